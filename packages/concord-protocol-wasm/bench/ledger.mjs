@@ -0,0 +1,116 @@
+// Compares the stateful `Ledger` class with the free functions, which convert
+// the whole container across the JS boundary on every call.
+//
+//   npm run bench -- [entries] [entriesPerCommit]
+
+import { createRequire } from "node:module";
+import { resolve } from "node:path";
+import { performance } from "node:perf_hooks";
+
+const require = createRequire(import.meta.url);
+const concord = require(resolve("target", "bench-pkg", "concord_protocol_wasm.js"));
+
+const ENTRIES = Number(process.argv[2] ?? 500);
+const PER_COMMIT = Number(process.argv[3] ?? 10);
+const TIMESTAMP = "2026-01-01T00:00:00Z";
+
+function entry(index) {
+  return {
+    kind: "concord/bench/item",
+    timestamp: TIMESTAMP,
+    author: "bench",
+    payload: { index, label: `item-${index}` },
+    signature: null,
+  };
+}
+
+function time(label, run) {
+  const start = performance.now();
+  const result = run();
+  const elapsed = performance.now() - start;
+  console.log(`${label.padEnd(40)} ${elapsed.toFixed(1).padStart(10)} ms`);
+  return result;
+}
+
+function buildWithFunctions() {
+  let ledger = concord.create_ledger(null, TIMESTAMP);
+  let pending = [];
+  for (let index = 0; index < ENTRIES; index += 1) {
+    const appended = concord.append_entry(ledger, entry(index));
+    ledger = appended.ledger;
+    pending.push(appended.entryId);
+    if (pending.length === PER_COMMIT) {
+      const { commitId, commit } = concord.create_commit({
+        ledger,
+        entries: pending,
+        timestamp: TIMESTAMP,
+      });
+      ledger = concord.append_commit(ledger, commitId, commit);
+      pending = [];
+    }
+  }
+  return ledger;
+}
+
+function buildWithClass() {
+  const ledger = new concord.Ledger(null, TIMESTAMP);
+  let pending = [];
+  for (let index = 0; index < ENTRIES; index += 1) {
+    pending.push(ledger.append_entry(entry(index)));
+    if (pending.length === PER_COMMIT) {
+      const { commitId, commit } = ledger.create_commit({
+        entries: pending,
+        timestamp: TIMESTAMP,
+      });
+      ledger.append_commit(commitId, commit);
+      pending = [];
+    }
+  }
+  return ledger;
+}
+
+// The staging workflow. The staged count is kept here, since reading
+// `ledger.staged` copies the staging area out of wasm.
+function buildWithStaging() {
+  const ledger = new concord.Ledger(null, TIMESTAMP);
+  let staged = 0;
+  for (let index = 0; index < ENTRIES; index += 1) {
+    ledger.stage(entry(index));
+    staged += 1;
+    if (staged === PER_COMMIT) {
+      ledger.commit(null, TIMESTAMP);
+      staged = 0;
+    }
+  }
+  return ledger;
+}
+
+console.log(`${ENTRIES} entries, ${PER_COMMIT} per commit\n`);
+
+const plain = time("build (free functions)", buildWithFunctions);
+const handle = time("build (Ledger)", buildWithClass);
+const staging = time("build (Ledger stage/commit)", buildWithStaging);
+const expected = JSON.stringify(plain);
+if (expected !== JSON.stringify(handle.export())) {
+  throw new Error("free functions and Ledger produced different ledgers");
+}
+if (expected !== JSON.stringify(staging.export())) {
+  throw new Error("free functions and Ledger staging produced different ledgers");
+}
+staging.free();
+
+time("get_replay_entries x10 (free functions)", () => {
+  for (let run = 0; run < 10; run += 1) concord.get_replay_entries(plain);
+});
+time("get_replay_entries x10 (Ledger)", () => {
+  for (let run = 0; run < 10; run += 1) handle.get_replay_entries();
+});
+time("validate_ledger x10 (free functions)", () => {
+  for (let run = 0; run < 10; run += 1) concord.validate_ledger(plain, null);
+});
+time("validate x10 (Ledger)", () => {
+  for (let run = 0; run < 10; run += 1) handle.validate(null);
+});
+time("export (Ledger)", () => handle.export());
+
+handle.free();
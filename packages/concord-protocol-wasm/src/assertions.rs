@@ -0,0 +1,163 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+
+use crate::canonical::canonical_stringify;
+use crate::diagnostics::ValidationError;
+use crate::error::{ConcordError, Result};
+use crate::types::ValidationResult;
+use crate::validate::join_messages;
+
+pub const ASSERTION_ENTRY_KIND: &str = "assertions";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AssertionSubject {
+    pub kind: String,
+    pub id: String,
+}
+
+/// A signed claim about a subject, carried by `assertions` entries.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Assertion {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub subject: AssertionSubject,
+    pub claim: String,
+    /// `Some(Value::Null)` is an explicit `null` payload, which is signed;
+    /// `None` means the field is absent and left out of the signing payload.
+    #[serde(
+        default,
+        deserialize_with = "present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub payload: Option<Value>,
+    #[serde(rename = "assertedBy")]
+    pub asserted_by: String,
+    #[serde(rename = "assertedAt")]
+    pub asserted_at: f64,
+    #[serde(default)]
+    pub signature: Option<String>,
+}
+
+fn present<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<Value>, D::Error> {
+    Value::deserialize(deserializer).map(Some)
+}
+
+fn non_empty_string(value: Option<&Value>) -> bool {
+    matches!(value, Some(Value::String(value)) if !value.is_empty())
+}
+
+pub(crate) fn assertion_errors(value: &Value) -> Vec<ValidationError> {
+    let invalid =
+        |message: &str, path: &str| ValidationError::new("INVALID_ASSERTION", message).at(path);
+    let assertion = match value.as_object() {
+        Some(assertion) => assertion,
+        None => {
+            return vec![ValidationError::new(
+                "INVALID_ASSERTION",
+                "Assertion must be an object",
+            )]
+        }
+    };
+    let mut errors: Vec<ValidationError> = Vec::new();
+    match assertion.get("subject").and_then(Value::as_object) {
+        Some(subject) => {
+            if !non_empty_string(subject.get("kind")) {
+                errors.push(invalid(
+                    "Assertion.subject.kind must be a non-empty string",
+                    "subject.kind",
+                ));
+            }
+            if !non_empty_string(subject.get("id")) {
+                errors.push(invalid(
+                    "Assertion.subject.id must be a non-empty string",
+                    "subject.id",
+                ));
+            }
+        }
+        None => errors.push(invalid("Assertion.subject must be an object", "subject")),
+    }
+    if !non_empty_string(assertion.get("claim")) {
+        errors.push(invalid(
+            "Assertion.claim must be a non-empty string",
+            "claim",
+        ));
+    }
+    if !non_empty_string(assertion.get("assertedBy")) {
+        errors.push(invalid(
+            "Assertion.assertedBy must be a non-empty string",
+            "assertedBy",
+        ));
+    }
+    if !assertion.get("assertedAt").is_some_and(Value::is_number) {
+        errors.push(invalid(
+            "Assertion.assertedAt must be a finite number",
+            "assertedAt",
+        ));
+    }
+    if let Some(payload) = assertion.get("payload") {
+        if let Err(error) = canonical_stringify(payload) {
+            errors.push(
+                ValidationError::new("INVALID_ASSERTION_PAYLOAD", error.message()).at("payload"),
+            );
+        }
+    }
+    errors
+}
+
+/// Validate assertion shape and canonicalizability.
+pub fn validate_assertion(value: &Value) -> ValidationResult {
+    ValidationResult::from_errors(assertion_errors(value))
+}
+
+impl Assertion {
+    /// Parses an assertion after shape validation, reporting problems as
+    /// `INVALID_ASSERTION`.
+    pub fn from_value(value: &Value) -> Result<Assertion> {
+        let errors = assertion_errors(value);
+        if !errors.is_empty() {
+            return Err(ConcordError::InvalidAssertion {
+                message: join_messages(&errors),
+                errors,
+            });
+        }
+        serde_json::from_value(value.clone()).map_err(|error| ConcordError::InvalidAssertion {
+            message: error.to_string(),
+            errors: Vec::new(),
+        })
+    }
+}
+
+/// Returns the assertion fields used for signing (excludes id, signature).
+pub fn get_assertion_core(assertion: &Assertion) -> Value {
+    let mut subject = Map::new();
+    subject.insert(
+        "kind".to_string(),
+        Value::from(assertion.subject.kind.clone()),
+    );
+    subject.insert("id".to_string(), Value::from(assertion.subject.id.clone()));
+
+    let mut core = Map::new();
+    core.insert("subject".to_string(), Value::Object(subject));
+    core.insert("claim".to_string(), Value::from(assertion.claim.clone()));
+    core.insert(
+        "assertedBy".to_string(),
+        Value::from(assertion.asserted_by.clone()),
+    );
+    core.insert("assertedAt".to_string(), Value::from(assertion.asserted_at));
+    if let Some(payload) = &assertion.payload {
+        core.insert("payload".to_string(), payload.clone());
+    }
+    Value::Object(core)
+}
+
+/// Canonical signing payload for an assertion.
+pub fn get_assertion_signing_payload(assertion: &Assertion) -> Result<String> {
+    canonical_stringify(&get_assertion_core(assertion))
+}
+
+/// Canonical signing payload bytes for an assertion.
+pub fn get_assertion_signing_bytes(assertion: &Assertion) -> Result<Vec<u8>> {
+    Ok(get_assertion_signing_payload(assertion)?.into_bytes())
+}
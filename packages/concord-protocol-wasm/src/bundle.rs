@@ -0,0 +1,217 @@
+//! Sync bundles: the commits and entries one peer has beyond a base commit
+//! the other already holds.
+
+use std::collections::BTreeSet;
+
+use crate::error::{ConcordError, Result};
+use crate::ids::{derive_commit_id, derive_entry_id};
+use crate::ledger::{append_commit_strict, append_entry, get_commit_chain, get_history};
+use crate::types::{Bundle, CreatedCommit, LedgerContainer, BUNDLE_FORMAT, BUNDLE_VERSION};
+
+/// Bundles the commits reachable from head but not from `base`, with the
+/// entries they reference.
+///
+/// `base` must be on the chain; for a peer whose head has diverged, use the
+/// `lastCommonCommit` from `diff_ledgers`. With no base the bundle carries
+/// the whole history.
+pub fn create_bundle(ledger: &LedgerContainer, base: Option<&str>) -> Result<Bundle> {
+    let chain = get_commit_chain(ledger)?;
+    let seen: BTreeSet<String> = match base {
+        Some(base) => {
+            if !chain.iter().any(|commit_id| commit_id == base) {
+                return Err(ConcordError::CursorNotOnChain {
+                    commit_id: base.to_string(),
+                });
+            }
+            get_history(ledger, base)?.into_iter().collect()
+        }
+        None => BTreeSet::new(),
+    };
+
+    let mut bundle = Bundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION.to_string(),
+        base: base.map(str::to_string),
+        head: ledger.head.clone(),
+        commits: Vec::new(),
+        entries: Default::default(),
+    };
+    for commit_id in chain.into_iter().filter(|id| !seen.contains(id)) {
+        let commit = ledger.commits[&commit_id].clone();
+        for entry_id in &commit.entries {
+            let entry = ledger
+                .entries
+                .get(entry_id)
+                .ok_or_else(|| ConcordError::MissingEntry {
+                    entry_id: entry_id.clone(),
+                })?;
+            bundle.entries.insert(entry_id.clone(), entry.clone());
+        }
+        bundle.commits.push(CreatedCommit { commit_id, commit });
+    }
+    Ok(bundle)
+}
+
+fn invalid(message: impl Into<String>) -> ConcordError {
+    ConcordError::InvalidBundle {
+        message: message.into(),
+    }
+}
+
+fn check_bundle_shape(ledger: &LedgerContainer, bundle: &Bundle) -> Result<()> {
+    if bundle.format != BUNDLE_FORMAT {
+        return Err(invalid(format!(
+            "Bundle.format must be \"{}\"",
+            BUNDLE_FORMAT
+        )));
+    }
+    if bundle.version != BUNDLE_VERSION {
+        return Err(invalid(format!(
+            "Bundle.version must be \"{}\"",
+            BUNDLE_VERSION
+        )));
+    }
+    match &bundle.base {
+        Some(base) if !ledger.commits.contains_key(base) => {
+            return Err(ConcordError::MissingCommit {
+                commit_id: base.clone(),
+            });
+        }
+        Some(_) => {}
+        // A full-history bundle must start from this ledger's genesis.
+        None => {
+            let shares_genesis = bundle
+                .commits
+                .first()
+                .is_some_and(|created| ledger.commits.contains_key(&created.commit_id));
+            if !shares_genesis {
+                return Err(ConcordError::UnrelatedHistories {
+                    ours: ledger.head.clone(),
+                    theirs: bundle.head.clone(),
+                });
+            }
+        }
+    }
+    for (entry_id, entry) in &bundle.entries {
+        let derived = derive_entry_id(entry)?;
+        if &derived != entry_id {
+            return Err(ConcordError::EntryIdMismatch {
+                entry_id: entry_id.clone(),
+                derived,
+            });
+        }
+    }
+    for created in &bundle.commits {
+        let derived = derive_commit_id(&created.commit)?;
+        if derived != created.commit_id {
+            return Err(ConcordError::CommitIdMismatch {
+                commit_id: created.commit_id.clone(),
+                derived,
+            });
+        }
+    }
+    let referenced: BTreeSet<&String> = bundle
+        .commits
+        .iter()
+        .flat_map(|created| created.commit.entries.iter())
+        .collect();
+    if let Some(entry_id) = bundle
+        .entries
+        .keys()
+        .find(|entry_id| !referenced.contains(entry_id))
+    {
+        return Err(invalid(format!(
+            "Bundle entry {} is not referenced by any bundle commit",
+            entry_id
+        )));
+    }
+    Ok(())
+}
+
+/// Adds the bundle's entries and commits and checks that the bundle head
+/// descends from `head`, recording what was added so a failure can be
+/// rolled back.
+fn import_bundle(
+    ledger: &mut LedgerContainer,
+    bundle: &Bundle,
+    head: &str,
+    added_entries: &mut Vec<String>,
+    added_commits: &mut Vec<String>,
+) -> Result<()> {
+    for (entry_id, entry) in &bundle.entries {
+        if ledger.entries.contains_key(entry_id) {
+            continue;
+        }
+        added_entries.push(append_entry(ledger, entry.clone())?);
+    }
+    for created in &bundle.commits {
+        if ledger.commits.contains_key(&created.commit_id) {
+            continue;
+        }
+        append_commit_strict(ledger, created.commit_id.clone(), created.commit.clone())?;
+        added_commits.push(created.commit_id.clone());
+    }
+
+    if !ledger.commits.contains_key(&bundle.head) {
+        return Err(invalid(format!(
+            "Bundle head {} is not among its commits",
+            bundle.head
+        )));
+    }
+    let history: BTreeSet<String> = get_history(ledger, &bundle.head)?.into_iter().collect();
+    if let Some(commit_id) = added_commits
+        .iter()
+        .find(|commit_id| !history.contains(*commit_id))
+    {
+        return Err(invalid(format!(
+            "Bundle commit {} is not reachable from the bundle head",
+            commit_id
+        )));
+    }
+    if !history.contains(head) {
+        return Err(ConcordError::NotFastForward {
+            head: head.to_string(),
+            target: bundle.head.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// Imports a bundle and fast-forwards head to the bundle head.
+///
+/// Every EntryID and CommitID is re-derived, each commit goes through the
+/// `append_commit_strict` checks (so its parents and entries must already be
+/// in the ledger or earlier in the bundle), and the current head must be an
+/// ancestor of the bundle head. Commits and entries the ledger already holds
+/// are skipped. The ledger is left untouched when any check fails. Returns
+/// the CommitIDs that were added, in replay order.
+pub fn apply_bundle(ledger: &mut LedgerContainer, bundle: &Bundle) -> Result<Vec<String>> {
+    check_bundle_shape(ledger, bundle)?;
+
+    let head = ledger.head.clone();
+    let mut added_entries: Vec<String> = Vec::new();
+    let mut added_commits: Vec<String> = Vec::new();
+    let result = import_bundle(
+        ledger,
+        bundle,
+        &head,
+        &mut added_entries,
+        &mut added_commits,
+    );
+    match result {
+        Ok(()) => {
+            ledger.head = bundle.head.clone();
+            Ok(added_commits)
+        }
+        Err(error) => {
+            for entry_id in &added_entries {
+                ledger.entries.remove(entry_id);
+            }
+            for commit_id in &added_commits {
+                ledger.commits.remove(commit_id);
+            }
+            ledger.head = head;
+            Err(error)
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use serde_json::{Number, Value};
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+
+use crate::error::{ConcordError, Result};
+
+/// Formats a finite number exactly as ECMAScript `Number::toString` (and
+/// therefore `JSON.stringify`) does.
+///
+/// Integers outside the safe range are rounded to the nearest f64 first, as a
+/// JavaScript runtime would when parsing the same literal.
+fn format_number(number: &Number) -> Result<String> {
+    match number.as_f64() {
+        Some(value) if value.is_finite() => Ok(format_f64(value)),
+        _ => Err(ConcordError::InvalidJson {
+            message: "Cannot canonicalize non-finite number".to_string(),
+        }),
+    }
+}
+
+fn format_f64(value: f64) -> String {
+    // Covers negative zero, which ECMAScript prints as "0".
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    if value < 0.0 {
+        return format!("-{}", format_f64(-value));
+    }
+
+    // `{:e}` yields the shortest round-tripping digits `d.ddd` and exponent,
+    // i.e. `s` and `n - 1` in the terms of ECMA-262 Number::toString.
+    let scientific = format!("{:e}", value);
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("LowerExp output always contains an exponent");
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let exponent: i32 = exponent.parse().expect("LowerExp exponent is an integer");
+    let k = digits.len() as i32;
+    let n = exponent + 1;
+
+    if k <= n && n <= 21 {
+        let mut out = digits;
+        out.push_str(&"0".repeat((n - k) as usize));
+        out
+    } else if 0 < n && n <= 21 {
+        let (int, frac) = digits.split_at(n as usize);
+        format!("{}.{}", int, frac)
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat((-n) as usize), digits)
+    } else {
+        let sign = if n - 1 < 0 { '-' } else { '+' };
+        let (first, rest) = digits.split_at(1);
+        if rest.is_empty() {
+            format!("{}e{}{}", first, sign, (n - 1).abs())
+        } else {
+            format!("{}.{}e{}{}", first, rest, sign, (n - 1).abs())
+        }
+    }
+}
+
+/// Orders keys by UTF-16 code units, as `Array.prototype.sort` does.
+///
+/// Rust's `str` ordering compares code points, which places astral-plane
+/// characters (surrogate pairs in JavaScript) after U+E000..U+FFFF.
+fn compare_utf16(a: &str, b: &str) -> Ordering {
+    a.encode_utf16().cmp(b.encode_utf16())
+}
+
+fn write_canonical(value: &Value, out: &mut String) -> Result<()> {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+        Value::Number(number) => out.push_str(&format_number(number)?),
+        Value::String(value) => out.push_str(&serde_json::to_string(value)?),
+        Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out)?;
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort_by(|a, b| compare_utf16(a, b));
+            out.push('{');
+            for (index, key) in keys.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::to_string(key)?);
+                out.push(':');
+                write_canonical(&map[key], out)?;
+            }
+            out.push('}');
+        }
+    }
+    Ok(())
+}
+
+/// Canonical JSON serialization with keys sorted by UTF-16 code units.
+///
+/// Output is byte-identical to `canonicalStringify` in `@ternent/concord-protocol`,
+/// including key order and ECMAScript number formatting.
+pub fn canonical_stringify(value: &Value) -> Result<String> {
+    let mut out = String::new();
+    write_canonical(value, &mut out)?;
+    Ok(out)
+}
+
+pub(crate) fn hash_canonical_string(canonical: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(canonical.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// SHA-256 hash of the canonical JSON serialization.
+pub fn hash_data(value: &Value) -> Result<String> {
+    let canonical = canonical_stringify(value)?;
+    Ok(hash_canonical_string(&canonical))
+}
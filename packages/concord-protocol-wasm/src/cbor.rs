@@ -0,0 +1,355 @@
+//! Compact binary (CBOR) encoding of ledger containers.
+//!
+//! The encoding mirrors the JSON container field for field, with two
+//! differences that keep it compact:
+//!
+//! - EntryIDs, CommitIDs and Merkle roots (map keys, `head`, `parent`,
+//!   `mergeParents`, commit `entries` and `entriesRoot`) are 32-byte byte
+//!   strings instead of 64-character hex. An ID that is not lowercase
+//!   SHA-256 hex is kept as a text string, so even invalid ledgers
+//!   round-trip.
+//! - Everything else is the CBOR equivalent of its JSON value.
+//!
+//! Output is deterministic (RFC 8949 §4.2.1): definite lengths, shortest
+//! integer and lossless float widths, and map keys sorted by their encoded
+//! bytes. IDs are still derived from canonical JSON, so decoding yields the
+//! exact JSON container that was encoded.
+
+use ciborium::value::Value as Cbor;
+use serde_json::{Map, Number, Value};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use crate::error::{ConcordError, Result};
+use crate::types::{Commit, Entry, LedgerContainer};
+
+const HASH_BYTES: usize = 32;
+
+fn invalid(message: impl Into<String>) -> ConcordError {
+    ConcordError::InvalidCbor {
+        message: message.into(),
+    }
+}
+
+fn encode_id(id: &str) -> Cbor {
+    let is_hash = id.len() == HASH_BYTES * 2
+        && id
+            .bytes()
+            .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(&byte));
+    if !is_hash {
+        return Cbor::Text(id.to_string());
+    }
+    let bytes = (0..HASH_BYTES)
+        .map(|index| u8::from_str_radix(&id[index * 2..index * 2 + 2], 16).unwrap())
+        .collect();
+    Cbor::Bytes(bytes)
+}
+
+fn decode_id(value: Cbor, field: &str) -> Result<String> {
+    match value {
+        Cbor::Bytes(bytes) if bytes.len() == HASH_BYTES => {
+            Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+        }
+        Cbor::Text(id) => Ok(id),
+        _ => Err(invalid(format!(
+            "{} must be a 32-byte hash or a string",
+            field
+        ))),
+    }
+}
+
+fn encode_ids(ids: &[String]) -> Cbor {
+    Cbor::Array(ids.iter().map(|id| encode_id(id)).collect())
+}
+
+fn decode_ids(value: Cbor, field: &str) -> Result<Vec<String>> {
+    match value {
+        Cbor::Array(items) => items
+            .into_iter()
+            .map(|item| decode_id(item, field))
+            .collect(),
+        _ => Err(invalid(format!("{} must be an array", field))),
+    }
+}
+
+fn encoded_key(key: &Cbor) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(key, &mut bytes).expect("writing to a Vec cannot fail");
+    bytes
+}
+
+/// A map with keys in deterministic (encoded bytewise) order.
+fn sorted_map(mut pairs: Vec<(Cbor, Cbor)>) -> Cbor {
+    pairs.sort_by_cached_key(|(key, _)| encoded_key(key));
+    Cbor::Map(pairs)
+}
+
+fn field(name: &str, value: Cbor) -> (Cbor, Cbor) {
+    (Cbor::Text(name.to_string()), value)
+}
+
+fn encode_json(value: &Value) -> Cbor {
+    match value {
+        Value::Null => Cbor::Null,
+        Value::Bool(value) => Cbor::Bool(*value),
+        Value::Number(number) => {
+            if let Some(value) = number.as_u64() {
+                Cbor::Integer(value.into())
+            } else if let Some(value) = number.as_i64() {
+                Cbor::Integer(value.into())
+            } else {
+                Cbor::Float(number.as_f64().unwrap_or(f64::NAN))
+            }
+        }
+        Value::String(value) => Cbor::Text(value.clone()),
+        Value::Array(items) => Cbor::Array(items.iter().map(encode_json).collect()),
+        Value::Object(fields) => sorted_map(
+            fields
+                .iter()
+                .map(|(key, value)| field(key, encode_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+fn decode_json(value: Cbor) -> Result<Value> {
+    Ok(match value {
+        Cbor::Null => Value::Null,
+        Cbor::Bool(value) => Value::Bool(value),
+        Cbor::Integer(value) => {
+            let value = i128::from(value);
+            if let Ok(value) = u64::try_from(value) {
+                Value::from(value)
+            } else if let Ok(value) = i64::try_from(value) {
+                Value::from(value)
+            } else {
+                return Err(invalid("Integer is out of the JSON range"));
+            }
+        }
+        Cbor::Float(value) => {
+            Value::Number(Number::from_f64(value).ok_or_else(|| invalid("Float must be finite"))?)
+        }
+        Cbor::Text(value) => Value::String(value),
+        Cbor::Array(items) => {
+            Value::Array(items.into_iter().map(decode_json).collect::<Result<_>>()?)
+        }
+        Cbor::Map(pairs) => {
+            let mut fields = Map::new();
+            for (key, value) in pairs {
+                match key {
+                    Cbor::Text(key) => {
+                        fields.insert(key, decode_json(value)?);
+                    }
+                    _ => return Err(invalid("Object keys must be strings")),
+                }
+            }
+            Value::Object(fields)
+        }
+        _ => return Err(invalid("Value has no JSON equivalent")),
+    })
+}
+
+fn optional_text(value: &Option<String>) -> Cbor {
+    value.clone().map_or(Cbor::Null, Cbor::Text)
+}
+
+/// The fields of a CBOR map, consumed by name so leftovers can be rejected.
+struct Fields {
+    name: &'static str,
+    fields: BTreeMap<String, Cbor>,
+}
+
+impl Fields {
+    fn new(value: Cbor, name: &'static str) -> Result<Self> {
+        let pairs = match value {
+            Cbor::Map(pairs) => pairs,
+            _ => return Err(invalid(format!("{} must be a map", name))),
+        };
+        let mut fields = BTreeMap::new();
+        for (key, value) in pairs {
+            match key {
+                Cbor::Text(key) => {
+                    fields.insert(key, value);
+                }
+                _ => return Err(invalid(format!("{} keys must be strings", name))),
+            }
+        }
+        Ok(Fields { name, fields })
+    }
+
+    fn optional(&mut self, key: &str) -> Option<Cbor> {
+        self.fields.remove(key)
+    }
+
+    fn required(&mut self, key: &str) -> Result<Cbor> {
+        self.optional(key)
+            .ok_or_else(|| invalid(format!("{}.{} is required", self.name, key)))
+    }
+
+    fn text(&mut self, key: &str) -> Result<String> {
+        match self.required(key)? {
+            Cbor::Text(value) => Ok(value),
+            _ => Err(invalid(format!("{}.{} must be a string", self.name, key))),
+        }
+    }
+
+    /// A nullable JSON field; `null` reads back as `None`, as it does from
+    /// JSON.
+    fn optional_json(&mut self, key: &str) -> Result<Option<Value>> {
+        match decode_json(self.required(key)?)? {
+            Value::Null => Ok(None),
+            value => Ok(Some(value)),
+        }
+    }
+
+    fn optional_text(&mut self, key: &str) -> Result<Option<String>> {
+        match self.optional(key) {
+            None | Some(Cbor::Null) => Ok(None),
+            Some(Cbor::Text(value)) => Ok(Some(value)),
+            Some(_) => Err(invalid(format!(
+                "{}.{} must be a string or null",
+                self.name, key
+            ))),
+        }
+    }
+
+    fn finish<T>(self, value: T) -> Result<T> {
+        match self.fields.keys().next() {
+            Some(key) => Err(invalid(format!(
+                "{}.{} is not a known field",
+                self.name, key
+            ))),
+            None => Ok(value),
+        }
+    }
+}
+
+fn optional_json(value: &Option<Value>) -> Cbor {
+    value.as_ref().map_or(Cbor::Null, encode_json)
+}
+
+fn encode_entry(entry: &Entry) -> Cbor {
+    sorted_map(vec![
+        field("kind", Cbor::Text(entry.kind.clone())),
+        field("timestamp", Cbor::Text(entry.timestamp.clone())),
+        field("author", Cbor::Text(entry.author.clone())),
+        field("payload", optional_json(&entry.payload)),
+        field("signature", optional_text(&entry.signature)),
+    ])
+}
+
+fn decode_entry(value: Cbor) -> Result<Entry> {
+    let mut fields = Fields::new(value, "Entry")?;
+    let entry = Entry {
+        kind: fields.text("kind")?,
+        timestamp: fields.text("timestamp")?,
+        author: fields.text("author")?,
+        payload: fields.optional_json("payload")?,
+        signature: fields.optional_text("signature")?,
+    };
+    fields.finish(entry)
+}
+
+fn encode_commit(commit: &Commit) -> Cbor {
+    let mut pairs = vec![
+        field(
+            "parent",
+            commit.parent.as_deref().map_or(Cbor::Null, encode_id),
+        ),
+        field("timestamp", Cbor::Text(commit.timestamp.clone())),
+        field("metadata", optional_json(&commit.metadata)),
+        field("entries", encode_ids(&commit.entries)),
+    ];
+    if !commit.merge_parents.is_empty() {
+        pairs.push(field("mergeParents", encode_ids(&commit.merge_parents)));
+    }
+    if let Some(root) = &commit.entries_root {
+        pairs.push(field("entriesRoot", encode_id(root)));
+    }
+    if let Some(signature) = &commit.signature {
+        pairs.push(field("signature", Cbor::Text(signature.clone())));
+    }
+    sorted_map(pairs)
+}
+
+fn decode_commit(value: Cbor) -> Result<Commit> {
+    let mut fields = Fields::new(value, "Commit")?;
+    let parent = match fields.required("parent")? {
+        Cbor::Null => None,
+        parent => Some(decode_id(parent, "Commit.parent")?),
+    };
+    let merge_parents = match fields.optional("mergeParents") {
+        Some(merge_parents) => decode_ids(merge_parents, "Commit.mergeParents")?,
+        None => Vec::new(),
+    };
+    let commit = Commit {
+        parent,
+        merge_parents,
+        timestamp: fields.text("timestamp")?,
+        metadata: fields.optional_json("metadata")?,
+        entries: decode_ids(fields.required("entries")?, "Commit.entries")?,
+        entries_root: fields
+            .optional("entriesRoot")
+            .map(|root| decode_id(root, "Commit.entriesRoot"))
+            .transpose()?,
+        signature: fields.optional_text("signature")?,
+    };
+    fields.finish(commit)
+}
+
+fn decode_records<T>(
+    value: Cbor,
+    field: &str,
+    decode: fn(Cbor) -> Result<T>,
+) -> Result<BTreeMap<String, T>> {
+    match value {
+        Cbor::Map(pairs) => pairs
+            .into_iter()
+            .map(|(key, value)| Ok((decode_id(key, field)?, decode(value)?)))
+            .collect(),
+        _ => Err(invalid(format!("{} must be a map", field))),
+    }
+}
+
+/// Deterministic CBOR encoding of a ledger container.
+pub fn encode_ledger_cbor(ledger: &LedgerContainer) -> Vec<u8> {
+    let commits = ledger
+        .commits
+        .iter()
+        .map(|(commit_id, commit)| (encode_id(commit_id), encode_commit(commit)))
+        .collect();
+    let entries = ledger
+        .entries
+        .iter()
+        .map(|(entry_id, entry)| (encode_id(entry_id), encode_entry(entry)))
+        .collect();
+    let container = sorted_map(vec![
+        field("format", Cbor::Text(ledger.format.clone())),
+        field("version", Cbor::Text(ledger.version.clone())),
+        field("head", encode_id(&ledger.head)),
+        field("commits", sorted_map(commits)),
+        field("entries", sorted_map(entries)),
+    ]);
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&container, &mut bytes).expect("writing to a Vec cannot fail");
+    bytes
+}
+
+/// Decodes a ledger container produced by [`encode_ledger_cbor`].
+///
+/// The container is not validated; call `validate_ledger` for untrusted
+/// input.
+pub fn decode_ledger_cbor(bytes: &[u8]) -> Result<LedgerContainer> {
+    let value: Cbor = ciborium::from_reader(bytes).map_err(|error| invalid(error.to_string()))?;
+    let mut fields = Fields::new(value, "Ledger")?;
+    let ledger = LedgerContainer {
+        format: fields.text("format")?,
+        version: fields.text("version")?,
+        head: decode_id(fields.required("head")?, "Ledger.head")?,
+        commits: decode_records(fields.required("commits")?, "Ledger.commits", decode_commit)?
+            .into(),
+        entries: decode_records(fields.required("entries")?, "Ledger.entries", decode_entry)?,
+        index: Default::default(),
+    };
+    fields.finish(ledger)
+}
@@ -0,0 +1,271 @@
+//! Checkpoint commits and history pruning.
+//!
+//! A checkpoint is an entry-less commit whose `metadata["concord/checkpoint"]`
+//! records `{ height, stateHash }`: the number of commits before it and a hash of
+//! the projection state after replaying them. Pruning drops the history
+//! behind a checkpoint, which then stands in for genesis as the ledger's
+//! base. Its missing parent is what marks it as a pruned base.
+//!
+//! Readers of a pruned ledger restore projection state from a snapshot
+//! matching `stateHash`, then replay from the checkpoint. Epoch validation
+//! still needs the genesis commit.
+
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+
+use crate::diagnostics::ValidationError;
+use crate::error::{ConcordError, Result};
+use crate::ids::derive_commit_id;
+use crate::ledger::{get_commit_chain, get_commit_parents, get_history};
+use crate::time::now_iso;
+use crate::types::{Checkpoint, Commit, CreatedCommit, LedgerContainer};
+
+/// Namespaced so it cannot collide with a caller's own metadata keys.
+const CHECKPOINT_KEY: &str = "concord/checkpoint";
+
+/// The checkpoint a commit records, if it is a well-formed checkpoint.
+pub fn get_checkpoint(commit: &Commit) -> Option<Checkpoint> {
+    let checkpoint = commit.metadata.as_ref()?.get(CHECKPOINT_KEY)?;
+    serde_json::from_value(checkpoint.clone()).ok()
+}
+
+/// True when the commit records a checkpoint.
+pub fn is_checkpoint_commit(commit: &Commit) -> bool {
+    get_checkpoint(commit).is_some()
+}
+
+/// True when the commit is a checkpoint whose history has been pruned.
+pub(crate) fn is_pruned_base(ledger: &LedgerContainer, commit: &Commit) -> bool {
+    commit.merge_parents.is_empty()
+        && commit
+            .parent
+            .as_ref()
+            .is_some_and(|parent| !ledger.commits.contains_key(parent))
+        && is_checkpoint_commit(commit)
+}
+
+/// The height of a ledger's base: 0 for genesis, or the checkpoint height
+/// of a pruned base.
+pub(crate) fn base_height(ledger: &LedgerContainer, base: &Commit) -> u64 {
+    if is_pruned_base(ledger, base) {
+        get_checkpoint(base).map_or(0, |checkpoint| checkpoint.height)
+    } else {
+        0
+    }
+}
+
+/// The height a checkpoint at `commit_id` must record: the number of its
+/// ancestors, counting those pruned behind the base.
+pub(crate) fn expected_height(ledger: &LedgerContainer, commit_id: &str) -> Result<u64> {
+    let history = get_history(ledger, commit_id)?;
+    let base = &ledger.commits[&history[0]];
+    Ok(base_height(ledger, base) + history.len() as u64 - 1)
+}
+
+/// The heights the checkpoints on `chain` (replay order) must record,
+/// computed in one pass.
+///
+/// Each checkpoint's ancestors are counted by walking back only to the
+/// previous checkpoint's ancestry, which it contains whenever the previous
+/// checkpoint is an ancestor. A checkpoint on a branch that does not descend
+/// from the previous one falls back to a full history walk.
+pub(crate) fn expected_heights<'a>(
+    ledger: &'a LedgerContainer,
+    chain: &'a [String],
+) -> Result<Vec<(&'a String, u64)>> {
+    let base_height = chain
+        .first()
+        .map_or(0, |base| base_height(ledger, &ledger.commits[base]));
+    let mut heights = Vec::new();
+    // The previous checkpoint, and it with all of its ancestors.
+    let mut last: Option<&str> = None;
+    let mut covered: BTreeSet<&str> = BTreeSet::new();
+    for commit_id in chain.iter().skip(1) {
+        let commit = &ledger.commits[commit_id];
+        if !is_checkpoint_commit(commit) {
+            continue;
+        }
+        let mut fresh: BTreeSet<&str> = BTreeSet::new();
+        let mut reached_last = false;
+        let mut stack: Vec<&str> = parent_ids(commit);
+        while let Some(ancestor) = stack.pop() {
+            if covered.contains(ancestor) {
+                reached_last |= last == Some(ancestor);
+                continue;
+            }
+            let (ancestor, ancestor_commit) = match ledger.commits.get_key_value(ancestor) {
+                Some(found) => found,
+                None => continue,
+            };
+            if fresh.insert(ancestor) && !is_pruned_base(ledger, ancestor_commit) {
+                stack.extend(parent_ids(ancestor_commit));
+            }
+        }
+        if last.is_none() || reached_last {
+            heights.push((
+                commit_id,
+                base_height + (covered.len() + fresh.len()) as u64,
+            ));
+            covered.extend(fresh);
+            covered.insert(commit_id);
+            last = Some(commit_id);
+        } else {
+            heights.push((commit_id, expected_height(ledger, commit_id)?));
+        }
+    }
+    Ok(heights)
+}
+
+fn parent_ids(commit: &Commit) -> Vec<&str> {
+    commit
+        .parent
+        .iter()
+        .chain(commit.merge_parents.iter())
+        .map(String::as_str)
+        .collect()
+}
+
+/// Shape diagnostics for `metadata["concord/checkpoint"]`, if present.
+pub(crate) fn checkpoint_errors(commit: &Commit) -> Vec<ValidationError> {
+    let checkpoint = match commit
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get(CHECKPOINT_KEY))
+    {
+        Some(checkpoint) => checkpoint,
+        None => return Vec::new(),
+    };
+    let invalid = |message: &str, path: &str| {
+        ValidationError::new("INVALID_CHECKPOINT", message).at(path.to_string())
+    };
+    let mut errors = Vec::new();
+    if !checkpoint.get("height").is_some_and(Value::is_u64) {
+        errors.push(invalid(
+            "Checkpoint height must be a non-negative integer",
+            r#"metadata["concord/checkpoint"].height"#,
+        ));
+    }
+    if checkpoint
+        .get("stateHash")
+        .and_then(Value::as_str)
+        .is_none_or(str::is_empty)
+    {
+        errors.push(invalid(
+            "Checkpoint stateHash must be a non-empty string",
+            r#"metadata["concord/checkpoint"].stateHash"#,
+        ));
+    }
+    if commit.parent.is_none() || !commit.merge_parents.is_empty() {
+        errors.push(invalid(
+            "Checkpoint commits must have exactly one parent",
+            "parent",
+        ));
+    }
+    if !commit.entries.is_empty() {
+        errors.push(invalid(
+            "Checkpoint commits must not contain entries",
+            "entries",
+        ));
+    }
+    errors
+}
+
+/// Creates a checkpoint commit on head recording `state_hash`, the hash of
+/// the projection state after replaying every entry up to head.
+///
+/// `metadata`, if given, must be an object; its `concord/checkpoint` key
+/// is replaced.
+pub fn create_checkpoint_commit(
+    ledger: &LedgerContainer,
+    state_hash: String,
+    metadata: Option<Value>,
+    timestamp: Option<String>,
+) -> Result<CreatedCommit> {
+    if state_hash.is_empty() {
+        return Err(ConcordError::InvalidCheckpoint {
+            commit_id: ledger.head.clone(),
+            message: "Checkpoint stateHash must be a non-empty string".to_string(),
+        });
+    }
+    let height = get_commit_chain(ledger).and_then(|_| expected_height(ledger, &ledger.head))? + 1;
+    let mut merged = match metadata {
+        Some(Value::Object(map)) => map,
+        Some(Value::Null) | None => Map::new(),
+        Some(_) => return Err(ConcordError::InvalidMetadata),
+    };
+    merged.insert(
+        CHECKPOINT_KEY.to_string(),
+        serde_json::to_value(Checkpoint { height, state_hash })?,
+    );
+    let commit = Commit {
+        parent: Some(ledger.head.clone()),
+        merge_parents: Vec::new(),
+        timestamp: timestamp.unwrap_or_else(now_iso),
+        metadata: Some(Value::Object(merged)),
+        entries: Vec::new(),
+        entries_root: None,
+        signature: None,
+    };
+    let commit_id = derive_commit_id(&commit)?;
+    Ok(CreatedCommit { commit_id, commit })
+}
+
+/// Drops every commit behind `checkpoint_id`, and the entries only they
+/// reference, leaving the checkpoint as the ledger's base.
+///
+/// The checkpoint must be on the chain and every later commit must descend
+/// from it; otherwise `INVALID_CHECKPOINT` is thrown. Commits not reachable
+/// from head are dropped too. Uncommitted entries are kept.
+pub fn prune_ledger(ledger: &LedgerContainer, checkpoint_id: &str) -> Result<LedgerContainer> {
+    let chain = get_commit_chain(ledger)?;
+    if !chain.iter().any(|commit_id| commit_id == checkpoint_id) {
+        return Err(ConcordError::CursorNotOnChain {
+            commit_id: checkpoint_id.to_string(),
+        });
+    }
+    let invalid = |message: String| ConcordError::InvalidCheckpoint {
+        commit_id: checkpoint_id.to_string(),
+        message,
+    };
+    if !is_checkpoint_commit(&ledger.commits[checkpoint_id]) {
+        return Err(invalid(format!(
+            "Commit {} is not a checkpoint",
+            checkpoint_id
+        )));
+    }
+
+    let behind: BTreeSet<String> = get_history(ledger, checkpoint_id)?
+        .into_iter()
+        .filter(|commit_id| commit_id != checkpoint_id)
+        .collect();
+    let kept: BTreeSet<&String> = chain.iter().filter(|id| !behind.contains(*id)).collect();
+    for commit_id in kept.iter().filter(|id| **id != checkpoint_id) {
+        if let Some(parent) = get_commit_parents(&ledger.commits[*commit_id])
+            .into_iter()
+            .find(|parent| behind.contains(parent))
+        {
+            return Err(invalid(format!(
+                "Commit {} has parent {} behind the checkpoint",
+                commit_id, parent
+            )));
+        }
+    }
+
+    let mut pruned = ledger.clone();
+    pruned
+        .commits
+        .retain(|commit_id, _| kept.contains(commit_id));
+    let still_used: BTreeSet<&String> = pruned
+        .commits
+        .values()
+        .flat_map(|commit| commit.entries.iter())
+        .collect();
+    let dropped: BTreeSet<&String> = behind
+        .iter()
+        .flat_map(|commit_id| ledger.commits[commit_id].entries.iter())
+        .collect();
+    pruned
+        .entries
+        .retain(|entry_id, _| still_used.contains(entry_id) || !dropped.contains(entry_id));
+    Ok(pruned)
+}
@@ -0,0 +1,130 @@
+//! Structured validation diagnostics.
+//!
+//! Every validator reports [`ValidationError`]s with a `code` drawn from
+//! [`VALIDATION_ERROR_CODES`]. The set is closed: codes are only added in a
+//! release note, never renamed, so callers can match on them. A condition a
+//! [`ConcordError`](crate::ConcordError) also reports has the same code, so
+//! `INVALID_COMMIT` names both a rejected append and its diagnostics.
+//!
+//! | Code | Raised when |
+//! | --- | --- |
+//! | `LEDGER_FORMAT_INVALID` | `format` is not `concord-ledger` |
+//! | `LEDGER_VERSION_INVALID` | `version` is not `1.0` |
+//! | `LEDGER_HEAD_INVALID` | `head` is empty |
+//! | `MISSING_HEAD` | `head` does not name a commit |
+//! | `MISSING_COMMIT` | a parent link names a commit that is not in the ledger |
+//! | `INVALID_PARENT` | a parent link is not a CommitID |
+//! | `COMMIT_CHAIN_CYCLE` | parent links loop |
+//! | `GENESIS_INVALID` | the genesis commit breaks a genesis invariant |
+//! | `INVALID_COMMIT` | a commit field has the wrong shape |
+//! | `INVALID_CHECKPOINT` | a checkpoint has the wrong shape or height |
+//! | `COMMIT_ID_MISMATCH` | a commit is stored under a key that is not its CommitID |
+//! | `ENTRIES_ROOT_MISMATCH` | a commit `entriesRoot` is not the Merkle root of its `entries` |
+//! | `TIMESTAMP_INVALID` | an entry or commit timestamp is not RFC 3339 |
+//! | `COMMIT_TIMESTAMP_DECREASING` | a commit is timestamped before one of its parents |
+//! | `DUPLICATE_ENTRY_IN_COMMIT` | a commit lists the same EntryID twice |
+//! | `ENTRY_ALREADY_COMMITTED` | a chain commit lists an entry an earlier chain commit lists |
+//! | `MISSING_ENTRY` | a commit references an entry that is not in the ledger |
+//! | `INVALID_ENTRY` | an entry field has the wrong shape |
+//! | `INVALID_ENTRY_PAYLOAD` | an entry payload cannot be canonicalized |
+//! | `ENTRY_ID_MISMATCH` | an entry is stored under a key that is not its EntryID |
+//! | `ENTRY_SIGNATURE_MISSING` | signature checks are on and an entry is unsigned |
+//! | `ENTRY_SIGNATURE_INVALID` | signature checks are on and an entry signature does not verify |
+//! | `INVALID_ASSERTION` | an assertion field has the wrong shape |
+//! | `INVALID_ASSERTION_PAYLOAD` | an assertion payload cannot be canonicalized |
+//! | `ENTRY_TIMESTAMP_AFTER_COMMIT` | an entry is timestamped after a commit that lists it |
+//! | `EPOCH_GENESIS_MISSING` | the genesis commit has no epoch entry |
+//! | `EPOCH_GENESIS_MULTIPLE` | the genesis commit has more than one epoch entry |
+//! | `EPOCH_PREV_NULL_OUTSIDE_GENESIS` | a non-genesis epoch has `prevEpochId: null` |
+//! | `EPOCH_CHAIN_BROKEN` | epoch `prevEpochId` links do not form a chain |
+//! | `SIGNER_KEY_ID_MISMATCH` | an epoch `signerKeyId` does not match its author |
+//! | `EPOCH_ID_MISMATCH` | an epoch ID does not match its derivation |
+//! | `EPOCH_CREATED_AT_MISMATCH` | an epoch `createdAt` differs from its entry timestamp |
+//! | `EPOCH_ENTRY_SIGNATURE_INVALID` | the epoch signature callback rejected an entry |
+//! | `EPOCH_UNKNOWN_ENCRYPTION_KEY` | an encrypted payload names no known epoch |
+
+use serde::Serialize;
+
+/// Every code a [`ValidationError`] can carry.
+pub const VALIDATION_ERROR_CODES: &[&str] = &[
+    "LEDGER_FORMAT_INVALID",
+    "LEDGER_VERSION_INVALID",
+    "LEDGER_HEAD_INVALID",
+    "MISSING_HEAD",
+    "MISSING_COMMIT",
+    "INVALID_PARENT",
+    "COMMIT_CHAIN_CYCLE",
+    "GENESIS_INVALID",
+    "INVALID_COMMIT",
+    "INVALID_CHECKPOINT",
+    "COMMIT_ID_MISMATCH",
+    "ENTRIES_ROOT_MISMATCH",
+    "TIMESTAMP_INVALID",
+    "COMMIT_TIMESTAMP_DECREASING",
+    "DUPLICATE_ENTRY_IN_COMMIT",
+    "ENTRY_ALREADY_COMMITTED",
+    "MISSING_ENTRY",
+    "INVALID_ENTRY",
+    "INVALID_ENTRY_PAYLOAD",
+    "ENTRY_ID_MISMATCH",
+    "ENTRY_SIGNATURE_MISSING",
+    "ENTRY_SIGNATURE_INVALID",
+    "INVALID_ASSERTION",
+    "INVALID_ASSERTION_PAYLOAD",
+    "ENTRY_TIMESTAMP_AFTER_COMMIT",
+    "EPOCH_GENESIS_MISSING",
+    "EPOCH_GENESIS_MULTIPLE",
+    "EPOCH_PREV_NULL_OUTSIDE_GENESIS",
+    "EPOCH_CHAIN_BROKEN",
+    "SIGNER_KEY_ID_MISMATCH",
+    "EPOCH_ID_MISMATCH",
+    "EPOCH_CREATED_AT_MISMATCH",
+    "EPOCH_ENTRY_SIGNATURE_INVALID",
+    "EPOCH_UNKNOWN_ENCRYPTION_KEY",
+];
+
+/// A validation diagnostic with a stable code and the commit, entry and
+/// field it concerns.
+///
+/// `path` is a dotted path (`metadata.spec`, `entries[0]`) into the entry or
+/// commit named by `entryId` / `commitId`, or into the ledger container when
+/// neither is set.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ValidationError {
+    pub code: &'static str,
+    pub message: String,
+    #[serde(rename = "commitId", skip_serializing_if = "Option::is_none")]
+    pub commit_id: Option<String>,
+    #[serde(rename = "entryId", skip_serializing_if = "Option::is_none")]
+    pub entry_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+impl ValidationError {
+    pub fn new(code: &'static str, message: impl Into<String>) -> Self {
+        debug_assert!(VALIDATION_ERROR_CODES.contains(&code), "{}", code);
+        ValidationError {
+            code,
+            message: message.into(),
+            commit_id: None,
+            entry_id: None,
+            path: None,
+        }
+    }
+
+    pub fn with_commit(mut self, commit_id: &str) -> Self {
+        self.commit_id = Some(commit_id.to_string());
+        self
+    }
+
+    pub fn with_entry(mut self, entry_id: &str) -> Self {
+        self.entry_id = Some(entry_id.to_string());
+        self
+    }
+
+    pub fn at(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+}
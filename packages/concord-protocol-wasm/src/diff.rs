@@ -0,0 +1,72 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::error::Result;
+use crate::ledger::get_commit_chain;
+use crate::types::{Commit, HeadRelation, LedgerContainer, LedgerDiff, LedgerSideDiff};
+
+/// Commits in `ours` but not `theirs`: those on the chain in replay order,
+/// then any that head does not reach, by CommitID.
+fn unique_commits(
+    chain: &[String],
+    ours: &BTreeMap<String, Commit>,
+    theirs: &BTreeMap<String, Commit>,
+) -> Vec<String> {
+    let on_chain: BTreeSet<&String> = chain.iter().collect();
+    chain
+        .iter()
+        .chain(
+            ours.keys()
+                .filter(|commit_id| !on_chain.contains(commit_id)),
+        )
+        .filter(|commit_id| !theirs.contains_key(*commit_id))
+        .cloned()
+        .collect()
+}
+
+fn side_diff(chain: &[String], ours: &LedgerContainer, theirs: &LedgerContainer) -> LedgerSideDiff {
+    LedgerSideDiff {
+        commits: unique_commits(chain, &ours.commits, &theirs.commits),
+        entries: ours
+            .entries
+            .keys()
+            .filter(|entry_id| !theirs.entries.contains_key(*entry_id))
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Compares two ledgers by their content-addressed commits and entries.
+///
+/// `only_in_a` / `only_in_b` list what each side has that the other lacks,
+/// which is what a sync has to send. `last_common_commit` is the latest
+/// commit on `a`'s chain that is also on `b`'s, and `relation` tells whether
+/// either head is a fast-forward of the other.
+pub fn diff_ledgers(a: &LedgerContainer, b: &LedgerContainer) -> Result<LedgerDiff> {
+    let chain_a = get_commit_chain(a)?;
+    let chain_b = get_commit_chain(b)?;
+    let on_chain_b: BTreeSet<&String> = chain_b.iter().collect();
+
+    let last_common_commit = chain_a
+        .iter()
+        .rev()
+        .find(|commit_id| on_chain_b.contains(commit_id))
+        .cloned();
+    let relation = if a.head == b.head {
+        HeadRelation::Equal
+    } else if chain_a.contains(&b.head) {
+        HeadRelation::Ahead
+    } else if on_chain_b.contains(&a.head) {
+        HeadRelation::Behind
+    } else if last_common_commit.is_some() {
+        HeadRelation::Diverged
+    } else {
+        HeadRelation::Unrelated
+    };
+
+    Ok(LedgerDiff {
+        only_in_a: side_diff(&chain_a, a, b),
+        only_in_b: side_diff(&chain_b, b, a),
+        last_common_commit,
+        relation,
+    })
+}
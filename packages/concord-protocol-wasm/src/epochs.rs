@@ -0,0 +1,473 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+
+use crate::canonical::{canonical_stringify, hash_canonical_string};
+use crate::diagnostics::ValidationError;
+use crate::error::Result;
+use crate::ledger::get_commit_chain;
+use crate::time::is_before;
+use crate::types::{Entry, LedgerContainer};
+
+pub const EPOCH_TAG: &str = "concord-epoch@1.0";
+
+/// Epoch diagnostics share the ledger [`ValidationError`] shape.
+pub type EpochValidationError = ValidationError;
+
+fn commit_error(code: &'static str, message: &str, commit_id: &str) -> ValidationError {
+    ValidationError::new(code, message).with_commit(commit_id)
+}
+
+fn entry_error(
+    code: &'static str,
+    message: &str,
+    commit_id: &str,
+    entry_id: &str,
+) -> ValidationError {
+    commit_error(code, message, commit_id).with_entry(entry_id)
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct EpochValidationResult {
+    pub ok: bool,
+    pub errors: Vec<EpochValidationError>,
+    #[serde(rename = "legacyEpochPlacement")]
+    pub legacy_epoch_placement: bool,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct EpochChainItem {
+    #[serde(rename = "entryId")]
+    pub entry_id: String,
+    #[serde(rename = "commitId")]
+    pub commit_id: String,
+    pub entry: Entry,
+}
+
+/// Result of [`get_active_epoch`]: the latest epoch entry, or the errors that
+/// prevented one from being selected.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ActiveEpochResult {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epoch: Option<Entry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<EpochValidationError>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct EncryptionKeyValidationResult {
+    pub ok: bool,
+    pub errors: Vec<EpochValidationError>,
+}
+
+/// Checks an epoch entry's signature, given its EntryID and content. `Err`
+/// carries a description of a verifier failure and is reported like an
+/// invalid signature.
+pub type VerifyEntrySignature<'a> = &'a dyn Fn(&str, &Entry) -> std::result::Result<bool, String>;
+
+#[derive(Clone, Copy, Default)]
+pub struct EpochValidationOptions<'a> {
+    pub verify_entry_signature: Option<VerifyEntrySignature<'a>>,
+}
+
+/// Inputs to [`derive_epoch_id`].
+#[derive(Deserialize, Clone, Debug)]
+pub struct EpochIdParams {
+    #[serde(rename = "signerKeyId")]
+    pub signer_key_id: String,
+    #[serde(rename = "encryptionPublicKey")]
+    pub encryption_public_key: String,
+    #[serde(rename = "prevEpochId")]
+    pub prev_epoch_id: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+}
+
+/// ECMAScript WhiteSpace and LineTerminator, the set `\s` and `trim` use.
+/// Unlike `char::is_whitespace`, this excludes U+0085 and includes U+FEFF.
+fn is_js_whitespace(c: char) -> bool {
+    matches!(
+        c,
+        '\t' | '\n' | '\u{b}' | '\u{c}' | '\r' | ' ' | '\u{a0}' | '\u{1680}' | '\u{2000}'
+            ..='\u{200a}'
+                | '\u{2028}'
+                | '\u{2029}'
+                | '\u{202f}'
+                | '\u{205f}'
+                | '\u{3000}'
+                | '\u{feff}'
+    )
+}
+
+/// Strips all whitespace from an identity public key.
+pub fn canonicalize_identity_key(value: &str) -> String {
+    value.chars().filter(|c| !is_js_whitespace(*c)).collect()
+}
+
+/// Normalizes line endings and trims an age recipient.
+pub fn canonicalize_age_recipient(value: &str) -> String {
+    value
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .trim_matches(is_js_whitespace)
+        .to_string()
+}
+
+/// SHA-256 of the canonicalized identity key.
+pub fn derive_signer_key_id(public_identity_key: &str) -> String {
+    hash_canonical_string(&canonicalize_identity_key(public_identity_key))
+}
+
+fn epoch_id_from_parts(
+    signer_key_id: &str,
+    encryption_public_key: &str,
+    prev_epoch_id: Value,
+    created_at: &str,
+) -> Result<String> {
+    let canonical = canonical_stringify(&serde_json::json!({
+        "tag": EPOCH_TAG,
+        "createdAt": created_at,
+        "encryptionPublicKey": canonicalize_age_recipient(encryption_public_key),
+        "prevEpochId": prev_epoch_id,
+        "signerKeyId": signer_key_id,
+    }))?;
+    Ok(hash_canonical_string(&canonical))
+}
+
+/// Deterministic EpochID over the epoch tag, signer, recipient, predecessor and
+/// creation time.
+pub fn derive_epoch_id(params: &EpochIdParams) -> Result<String> {
+    epoch_id_from_parts(
+        &params.signer_key_id,
+        &params.encryption_public_key,
+        params
+            .prev_epoch_id
+            .clone()
+            .map(Value::String)
+            .unwrap_or(Value::Null),
+        &params.created_at,
+    )
+}
+
+fn epoch_payload(entry: &Entry) -> Option<&Map<String, Value>> {
+    if entry.kind != "epochs" {
+        return None;
+    }
+    match &entry.payload {
+        Some(Value::Object(payload)) if payload.get("type") == Some(&json_str("epoch")) => {
+            Some(payload)
+        }
+        _ => None,
+    }
+}
+
+fn json_str(value: &str) -> Value {
+    Value::String(value.to_string())
+}
+
+fn is_truthy(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(value)) => *value,
+        Some(Value::Number(number)) => number.as_f64().is_some_and(|n| n != 0.0),
+        Some(Value::String(value)) => !value.is_empty(),
+        Some(_) => true,
+    }
+}
+
+fn str_field<'a>(payload: &'a Map<String, Value>, key: &str) -> &'a str {
+    payload.get(key).and_then(Value::as_str).unwrap_or("")
+}
+
+/// Epoch entries in commit-chain order.
+pub fn get_epoch_chain(ledger: &LedgerContainer) -> Result<Vec<EpochChainItem>> {
+    let mut epochs = Vec::new();
+    for commit_id in get_commit_chain(ledger)? {
+        let commit = &ledger.commits[&commit_id];
+        for entry_id in &commit.entries {
+            if let Some(entry) = ledger.entries.get(entry_id) {
+                if epoch_payload(entry).is_some() {
+                    epochs.push(EpochChainItem {
+                        entry_id: entry_id.clone(),
+                        commit_id: commit_id.clone(),
+                        entry: entry.clone(),
+                    });
+                }
+            }
+        }
+    }
+    Ok(epochs)
+}
+
+/// The most recent epoch entry, once the epoch chain validates.
+pub fn get_active_epoch(
+    ledger: &LedgerContainer,
+    options: EpochValidationOptions<'_>,
+) -> Result<ActiveEpochResult> {
+    let validation = validate_ledger_epochs(ledger, options)?;
+    if !validation.ok {
+        return Ok(ActiveEpochResult {
+            ok: false,
+            epoch: None,
+            errors: validation.errors,
+        });
+    }
+    match get_epoch_chain(ledger)?.pop() {
+        Some(item) => Ok(ActiveEpochResult {
+            ok: true,
+            epoch: Some(item.entry),
+            errors: Vec::new(),
+        }),
+        None => Ok(ActiveEpochResult {
+            ok: false,
+            epoch: None,
+            errors: vec![commit_error(
+                "EPOCH_GENESIS_MISSING",
+                "Genesis commit must include exactly one epoch entry.",
+                &get_commit_chain(ledger)?[0],
+            )],
+        }),
+    }
+}
+
+/// Validate epoch placement, linkage, derived IDs and (optionally) signatures.
+///
+/// `ENTRY_TIMESTAMP_AFTER_COMMIT` compares RFC 3339 timestamps only. The
+/// TypeScript validator uses `Date.parse`, which also reads date-only and
+/// offset-less forms; those are never reported here.
+pub fn validate_ledger_epochs(
+    ledger: &LedgerContainer,
+    options: EpochValidationOptions<'_>,
+) -> Result<EpochValidationResult> {
+    let mut errors: Vec<EpochValidationError> = Vec::new();
+    let mut legacy_epoch_placement = false;
+
+    let chain = get_commit_chain(ledger)?;
+    let genesis_id = chain[0].as_str();
+    for commit_id in &chain {
+        let commit = &ledger.commits[commit_id];
+        for entry_id in &commit.entries {
+            if let Some(entry) = ledger.entries.get(entry_id) {
+                if is_before(&commit.timestamp, &entry.timestamp) {
+                    errors.push(entry_error(
+                        "ENTRY_TIMESTAMP_AFTER_COMMIT",
+                        "Entry timestamp must be on or before its commit timestamp.",
+                        commit_id,
+                        entry_id,
+                    ));
+                }
+            }
+        }
+    }
+
+    let epoch_entry_count = ledger
+        .entries
+        .values()
+        .filter(|entry| epoch_payload(entry).is_some())
+        .count();
+    let genesis_epoch_count = ledger.commits[genesis_id]
+        .entries
+        .iter()
+        .filter(|entry_id| {
+            ledger
+                .entries
+                .get(*entry_id)
+                .is_some_and(|entry| epoch_payload(entry).is_some())
+        })
+        .count();
+
+    if genesis_epoch_count == 0 {
+        errors.push(commit_error(
+            "EPOCH_GENESIS_MISSING",
+            "Genesis commit must include exactly one epoch entry.",
+            genesis_id,
+        ));
+        legacy_epoch_placement = epoch_entry_count > 0;
+    } else if genesis_epoch_count > 1 {
+        errors.push(commit_error(
+            "EPOCH_GENESIS_MULTIPLE",
+            "Genesis commit must include exactly one epoch entry.",
+            genesis_id,
+        ));
+    }
+
+    let mut last_epoch_id: Option<Value> = None;
+    let mut prev_null_epoch_count = 0;
+    for commit_id in &chain {
+        let commit = &ledger.commits[commit_id];
+        for entry_id in &commit.entries {
+            let entry = match ledger.entries.get(entry_id) {
+                Some(entry) => entry,
+                None => continue,
+            };
+            let payload = match epoch_payload(entry) {
+                Some(payload) => payload,
+                None => continue,
+            };
+            let is_genesis_epoch = commit_id == genesis_id;
+            let prev_epoch_id = payload.get("prevEpochId");
+            let push = |errors: &mut Vec<EpochValidationError>, code, message: &str| {
+                errors.push(entry_error(code, message, commit_id, entry_id));
+            };
+
+            if prev_epoch_id == Some(&Value::Null) {
+                prev_null_epoch_count += 1;
+            }
+            if !is_genesis_epoch && prev_epoch_id == Some(&Value::Null) {
+                push(
+                    &mut errors,
+                    "EPOCH_PREV_NULL_OUTSIDE_GENESIS",
+                    "Epoch prevEpochId must not be null outside genesis.",
+                );
+            }
+            if is_genesis_epoch && prev_epoch_id != Some(&Value::Null) {
+                push(
+                    &mut errors,
+                    "EPOCH_CHAIN_BROKEN",
+                    "Genesis epoch must have prevEpochId null.",
+                );
+            }
+            if let Some(last) = &last_epoch_id {
+                if prev_epoch_id != Some(last) {
+                    push(
+                        &mut errors,
+                        "EPOCH_CHAIN_BROKEN",
+                        "Epoch prevEpochId must equal the previous epochId.",
+                    );
+                }
+            }
+            if last_epoch_id.is_none() && !is_genesis_epoch && is_truthy(prev_epoch_id) {
+                legacy_epoch_placement = true;
+            }
+
+            let signer_key_id = derive_signer_key_id(&entry.author);
+            if payload.get("signerKeyId") != Some(&json_str(&signer_key_id)) {
+                push(
+                    &mut errors,
+                    "SIGNER_KEY_ID_MISMATCH",
+                    "Epoch signerKeyId does not match author identity.",
+                );
+            }
+
+            let derived_epoch_id = epoch_id_from_parts(
+                &signer_key_id,
+                str_field(payload, "encryptionPublicKey"),
+                prev_epoch_id.cloned().unwrap_or(Value::Null),
+                str_field(payload, "createdAt"),
+            )?;
+            if payload.get("epochId") != Some(&json_str(&derived_epoch_id)) {
+                push(
+                    &mut errors,
+                    "EPOCH_ID_MISMATCH",
+                    "EpochId does not match deterministic hash.",
+                );
+            }
+            if payload.get("encryptionKeyId") != payload.get("epochId") {
+                push(
+                    &mut errors,
+                    "EPOCH_ID_MISMATCH",
+                    "encryptionKeyId must equal epochId.",
+                );
+            }
+            if payload.get("createdAt") != Some(&json_str(&entry.timestamp)) {
+                push(
+                    &mut errors,
+                    "EPOCH_CREATED_AT_MISMATCH",
+                    "Epoch createdAt must equal entry timestamp.",
+                );
+            }
+
+            if let Some(verify) = options.verify_entry_signature {
+                match verify(entry_id, entry) {
+                    Ok(true) => {}
+                    Ok(false) => push(
+                        &mut errors,
+                        "EPOCH_ENTRY_SIGNATURE_INVALID",
+                        "Epoch entry signature invalid.",
+                    ),
+                    Err(error) => push(
+                        &mut errors,
+                        "EPOCH_ENTRY_SIGNATURE_INVALID",
+                        &format!("Epoch entry signature error: {}", error),
+                    ),
+                }
+            }
+
+            if is_truthy(payload.get("epochId")) {
+                last_epoch_id = payload.get("epochId").cloned();
+            }
+        }
+    }
+
+    if prev_null_epoch_count == 0 && epoch_entry_count > 0 {
+        errors.push(commit_error(
+            "EPOCH_CHAIN_BROKEN",
+            "No genesis epoch found with prevEpochId null.",
+            genesis_id,
+        ));
+    }
+
+    Ok(EpochValidationResult {
+        ok: errors.is_empty(),
+        errors,
+        legacy_epoch_placement,
+    })
+}
+
+/// Checks that encrypted payloads reference an epoch declared earlier in the
+/// chain.
+pub fn validate_ledger_encryption_key_ids(
+    ledger: &LedgerContainer,
+) -> Result<EncryptionKeyValidationResult> {
+    let mut errors: Vec<EpochValidationError> = Vec::new();
+    let mut known_epoch_ids: BTreeSet<String> = BTreeSet::new();
+
+    for commit_id in get_commit_chain(ledger)? {
+        let commit = &ledger.commits[&commit_id];
+        for entry_id in &commit.entries {
+            let entry = match ledger.entries.get(entry_id) {
+                Some(entry) => entry,
+                None => continue,
+            };
+            if let Some(payload) = epoch_payload(entry) {
+                if let Some(Value::String(epoch_id)) = payload.get("epochId") {
+                    known_epoch_ids.insert(epoch_id.clone());
+                }
+            }
+
+            let payload = match &entry.payload {
+                Some(Value::Object(payload)) => payload,
+                _ => continue,
+            };
+            let requires_key = payload.contains_key("permissionId")
+                || payload.contains_key("encrypted")
+                || payload.contains_key("secret");
+            let encryption_key_id = payload
+                .get("encryptionKeyId")
+                .and_then(Value::as_str)
+                .filter(|key_id| !key_id.is_empty());
+
+            match encryption_key_id {
+                None if requires_key => errors.push(entry_error(
+                    "EPOCH_UNKNOWN_ENCRYPTION_KEY",
+                    "Entry missing encryptionKeyId for encrypted payload.",
+                    &commit_id,
+                    entry_id,
+                )),
+                Some(key_id) if !known_epoch_ids.contains(key_id) => errors.push(entry_error(
+                    "EPOCH_UNKNOWN_ENCRYPTION_KEY",
+                    "Entry references unknown encryptionKeyId.",
+                    &commit_id,
+                    entry_id,
+                )),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(EncryptionKeyValidationResult {
+        ok: errors.is_empty(),
+        errors,
+    })
+}
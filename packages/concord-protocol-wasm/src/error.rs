@@ -0,0 +1,317 @@
+use serde_json::{json, Map, Value};
+use std::fmt;
+
+use crate::diagnostics::ValidationError;
+
+/// Every failure the protocol core can report.
+///
+/// Each variant has a stable machine-readable [`code`](ConcordError::code)
+/// and carries the IDs it concerns, exposed to JavaScript through
+/// [`context`](ConcordError::context).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConcordError {
+    /// A value cannot be canonicalized or (de)serialized as JSON.
+    InvalidJson {
+        message: String,
+    },
+    /// A host value does not have the shape an export expects.
+    Deserialization {
+        message: String,
+    },
+    InvalidMetadata,
+    MissingHead {
+        head: String,
+    },
+    CommitChainCycle {
+        commit_id: String,
+    },
+    MissingCommit {
+        commit_id: String,
+    },
+    InvalidParent {
+        message: String,
+    },
+    MissingEntry {
+        entry_id: String,
+    },
+    InvalidCommit {
+        message: String,
+        errors: Vec<ValidationError>,
+    },
+    DuplicateCommit {
+        commit_id: String,
+    },
+    CommitIdMismatch {
+        commit_id: String,
+        derived: String,
+    },
+    InvalidEntry {
+        message: String,
+        errors: Vec<ValidationError>,
+    },
+    InvalidEntryPayload {
+        message: String,
+    },
+    DuplicateEntry {
+        entry_id: String,
+    },
+    /// `commit()` was called with an empty staging area.
+    NothingStaged,
+    /// A replay cursor or bundle base is not on the chain from genesis to
+    /// head.
+    CursorNotOnChain {
+        commit_id: String,
+    },
+    /// Bytes are not a CBOR-encoded ledger container.
+    InvalidCbor {
+        message: String,
+    },
+    /// A commit cannot be used as a checkpoint.
+    InvalidCheckpoint {
+        commit_id: String,
+        message: String,
+    },
+    /// An inclusion proof was requested for a commit without `entriesRoot`.
+    MissingEntriesRoot {
+        commit_id: String,
+    },
+    /// An entry is not listed in the commit it was expected in.
+    EntryNotInCommit {
+        entry_id: String,
+        commit_id: String,
+    },
+    /// A commit lists an entry that a commit in its history already lists.
+    EntryAlreadyCommitted {
+        entry_id: String,
+        commit_id: String,
+    },
+    /// A retention root names neither a commit nor an entry in the ledger.
+    UnknownRoot {
+        root: String,
+    },
+    /// An NDJSON ledger stream is malformed at `line` (1-based).
+    InvalidNdjson {
+        line: usize,
+        message: String,
+    },
+    /// A record of an NDJSON ledger stream failed at `line` (1-based). The
+    /// code and message are those of `error`, whose context gains `line`.
+    AtNdjsonLine {
+        line: usize,
+        error: Box<ConcordError>,
+    },
+    /// A sync bundle is malformed or does not connect to the ledger.
+    InvalidBundle {
+        message: String,
+    },
+    /// An entry is stored under a key that is not its EntryID.
+    EntryIdMismatch {
+        entry_id: String,
+        derived: String,
+    },
+    /// Applying a bundle would move head to a commit that does not contain
+    /// the current head.
+    NotFastForward {
+        head: String,
+        target: String,
+    },
+    /// Two ledgers being merged share no commit.
+    UnrelatedHistories {
+        ours: String,
+        theirs: String,
+    },
+    InvalidAssertion {
+        message: String,
+        errors: Vec<ValidationError>,
+    },
+    InvalidPublicKey {
+        message: String,
+    },
+    InvalidSignature {
+        message: String,
+    },
+    EntrySignatureMissing,
+    EntrySignatureInvalid {
+        message: String,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, ConcordError>;
+
+impl ConcordError {
+    /// Stable error code, e.g. `MISSING_HEAD`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ConcordError::InvalidJson { .. } => "INVALID_JSON",
+            ConcordError::Deserialization { .. } => "DESERIALIZATION_FAILED",
+            ConcordError::InvalidMetadata => "INVALID_METADATA",
+            ConcordError::MissingHead { .. } => "MISSING_HEAD",
+            ConcordError::CommitChainCycle { .. } => "COMMIT_CHAIN_CYCLE",
+            ConcordError::MissingCommit { .. } => "MISSING_COMMIT",
+            ConcordError::InvalidParent { .. } => "INVALID_PARENT",
+            ConcordError::MissingEntry { .. } => "MISSING_ENTRY",
+            ConcordError::InvalidCommit { .. } => "INVALID_COMMIT",
+            ConcordError::DuplicateCommit { .. } => "DUPLICATE_COMMIT",
+            ConcordError::CommitIdMismatch { .. } => "COMMIT_ID_MISMATCH",
+            ConcordError::InvalidEntry { .. } => "INVALID_ENTRY",
+            ConcordError::InvalidEntryPayload { .. } => "INVALID_ENTRY_PAYLOAD",
+            ConcordError::DuplicateEntry { .. } => "DUPLICATE_ENTRY",
+            ConcordError::NothingStaged => "NOTHING_STAGED",
+            ConcordError::CursorNotOnChain { .. } => "CURSOR_NOT_ON_CHAIN",
+            ConcordError::InvalidCbor { .. } => "INVALID_CBOR",
+            ConcordError::InvalidCheckpoint { .. } => "INVALID_CHECKPOINT",
+            ConcordError::MissingEntriesRoot { .. } => "MISSING_ENTRIES_ROOT",
+            ConcordError::EntryNotInCommit { .. } => "ENTRY_NOT_IN_COMMIT",
+            ConcordError::EntryAlreadyCommitted { .. } => "ENTRY_ALREADY_COMMITTED",
+            ConcordError::UnknownRoot { .. } => "UNKNOWN_ROOT",
+            ConcordError::InvalidNdjson { .. } => "INVALID_NDJSON",
+            ConcordError::AtNdjsonLine { error, .. } => error.code(),
+            ConcordError::InvalidBundle { .. } => "INVALID_BUNDLE",
+            ConcordError::EntryIdMismatch { .. } => "ENTRY_ID_MISMATCH",
+            ConcordError::NotFastForward { .. } => "NOT_FAST_FORWARD",
+            ConcordError::UnrelatedHistories { .. } => "UNRELATED_HISTORIES",
+            ConcordError::InvalidAssertion { .. } => "INVALID_ASSERTION",
+            ConcordError::InvalidPublicKey { .. } => "INVALID_PUBLIC_KEY",
+            ConcordError::InvalidSignature { .. } => "INVALID_SIGNATURE",
+            ConcordError::EntrySignatureMissing => "ENTRY_SIGNATURE_MISSING",
+            ConcordError::EntrySignatureInvalid { .. } => "ENTRY_SIGNATURE_INVALID",
+        }
+    }
+
+    /// Human-readable description of the failure.
+    pub fn message(&self) -> String {
+        match self {
+            ConcordError::InvalidJson { message }
+            | ConcordError::Deserialization { message }
+            | ConcordError::InvalidParent { message }
+            | ConcordError::InvalidCbor { message }
+            | ConcordError::InvalidBundle { message }
+            | ConcordError::InvalidCommit { message, .. }
+            | ConcordError::InvalidEntry { message, .. }
+            | ConcordError::InvalidEntryPayload { message }
+            | ConcordError::InvalidCheckpoint { message, .. }
+            | ConcordError::InvalidAssertion { message, .. }
+            | ConcordError::InvalidPublicKey { message }
+            | ConcordError::InvalidSignature { message }
+            | ConcordError::EntrySignatureInvalid { message } => message.clone(),
+            ConcordError::InvalidMetadata => "metadata must be an object or null".to_string(),
+            ConcordError::MissingHead { head } => format!("Missing head commit {}", head),
+            ConcordError::CommitChainCycle { commit_id } => {
+                format!("Commit chain cycle detected at {}", commit_id)
+            }
+            ConcordError::MissingCommit { commit_id } => format!("Missing commit {}", commit_id),
+            ConcordError::MissingEntry { entry_id } => format!("Missing entry {}", entry_id),
+            ConcordError::DuplicateCommit { commit_id } => {
+                format!("Commit {} already exists", commit_id)
+            }
+            ConcordError::CommitIdMismatch { .. } => {
+                "CommitID does not match commit content".to_string()
+            }
+            ConcordError::DuplicateEntry { entry_id } => {
+                format!("Entry {} already exists", entry_id)
+            }
+            ConcordError::NothingStaged => "No staged entries to commit".to_string(),
+            ConcordError::CursorNotOnChain { commit_id } => {
+                format!("Commit {} is not on the current chain", commit_id)
+            }
+            ConcordError::EntryIdMismatch { .. } => {
+                "EntryID does not match entry content".to_string()
+            }
+            ConcordError::NotFastForward { head, target } => format!(
+                "Head {} is not an ancestor of {}; merge instead",
+                head, target
+            ),
+            ConcordError::UnrelatedHistories { ours, theirs } => format!(
+                "Ledger heads {} and {} share no common commit",
+                ours, theirs
+            ),
+            ConcordError::MissingEntriesRoot { commit_id } => {
+                format!("Commit {} has no entriesRoot", commit_id)
+            }
+            ConcordError::EntryNotInCommit {
+                entry_id,
+                commit_id,
+            } => format!("Entry {} is not in commit {}", entry_id, commit_id),
+            ConcordError::EntryAlreadyCommitted {
+                entry_id,
+                commit_id,
+            } => format!("Entry {} is already committed in {}", entry_id, commit_id),
+            ConcordError::UnknownRoot { root } => {
+                format!("Root {} is neither a commit nor an entry", root)
+            }
+            ConcordError::InvalidNdjson { line, message } => format!("Line {}: {}", line, message),
+            ConcordError::AtNdjsonLine { line, error } => {
+                format!("Line {}: {}", line, error.message())
+            }
+            ConcordError::EntrySignatureMissing => "Entry.signature is required".to_string(),
+        }
+    }
+
+    /// Structured fields beyond `code` and `message`, keyed as in the
+    /// JavaScript API (`commitId`, `entryId`, `errors`, ...).
+    pub fn context(&self) -> Map<String, Value> {
+        let context = match self {
+            ConcordError::AtNdjsonLine { line, error } => {
+                let mut context = error.context();
+                context.insert("line".to_string(), json!(line));
+                return context;
+            }
+            ConcordError::MissingHead { head } => json!({ "head": head }),
+            ConcordError::CommitChainCycle { commit_id }
+            | ConcordError::MissingCommit { commit_id }
+            | ConcordError::DuplicateCommit { commit_id }
+            | ConcordError::CursorNotOnChain { commit_id }
+            | ConcordError::MissingEntriesRoot { commit_id }
+            | ConcordError::InvalidCheckpoint { commit_id, .. } => json!({ "commitId": commit_id }),
+            ConcordError::EntryNotInCommit {
+                entry_id,
+                commit_id,
+            }
+            | ConcordError::EntryAlreadyCommitted {
+                entry_id,
+                commit_id,
+            } => json!({ "entryId": entry_id, "commitId": commit_id }),
+            ConcordError::CommitIdMismatch { commit_id, derived } => {
+                json!({ "commitId": commit_id, "derivedCommitId": derived })
+            }
+            ConcordError::EntryIdMismatch { entry_id, derived } => {
+                json!({ "entryId": entry_id, "derivedEntryId": derived })
+            }
+            ConcordError::UnknownRoot { root } => json!({ "root": root }),
+            ConcordError::InvalidNdjson { line, .. } => json!({ "line": line }),
+            ConcordError::NotFastForward { head, target } => {
+                json!({ "head": head, "target": target })
+            }
+            ConcordError::UnrelatedHistories { ours, theirs } => {
+                json!({ "ours": ours, "theirs": theirs })
+            }
+            ConcordError::MissingEntry { entry_id } | ConcordError::DuplicateEntry { entry_id } => {
+                json!({ "entryId": entry_id })
+            }
+            ConcordError::InvalidCommit { errors, .. }
+            | ConcordError::InvalidEntry { errors, .. }
+            | ConcordError::InvalidAssertion { errors, .. } => json!({ "errors": errors }),
+            _ => json!({}),
+        };
+        match context {
+            Value::Object(context) => context,
+            _ => Map::new(),
+        }
+    }
+}
+
+impl fmt::Display for ConcordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for ConcordError {}
+
+impl From<serde_json::Error> for ConcordError {
+    fn from(error: serde_json::Error) -> Self {
+        ConcordError::InvalidJson {
+            message: error.to_string(),
+        }
+    }
+}
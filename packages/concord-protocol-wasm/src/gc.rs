@@ -0,0 +1,78 @@
+//! Reachability reports and garbage collection of commits head does not
+//! reach and entries no kept commit lists, such as those left by a failed
+//! sync.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::error::{ConcordError, Result};
+use crate::ledger::{get_commit_chain, get_history};
+use crate::types::{LedgerContainer, ReachabilityReport};
+
+/// Reports commits head does not reach, entries no commit lists, and
+/// entries listed by more than one commit.
+///
+/// Entries listed only by orphan commits are not unreferenced, but
+/// `gc_ledger` drops them along with those commits.
+pub fn get_reachability_report(ledger: &LedgerContainer) -> Result<ReachabilityReport> {
+    let reachable: BTreeSet<String> = get_commit_chain(ledger)?.into_iter().collect();
+    let mut listed_by: BTreeMap<&String, BTreeSet<&String>> = BTreeMap::new();
+    for (commit_id, commit) in &ledger.commits {
+        for entry_id in &commit.entries {
+            listed_by.entry(entry_id).or_default().insert(commit_id);
+        }
+    }
+
+    Ok(ReachabilityReport {
+        orphan_commits: ledger
+            .commits
+            .keys()
+            .filter(|commit_id| !reachable.contains(*commit_id))
+            .cloned()
+            .collect(),
+        unreferenced_entries: ledger
+            .entries
+            .keys()
+            .filter(|entry_id| !listed_by.contains_key(entry_id))
+            .cloned()
+            .collect(),
+        shared_entries: listed_by
+            .into_iter()
+            .filter(|(_, commits)| commits.len() > 1)
+            .map(|(entry_id, commits)| (entry_id.clone(), commits.into_iter().cloned().collect()))
+            .collect(),
+    })
+}
+
+/// Returns a copy of the ledger holding only the history of head and the
+/// entries that history lists.
+///
+/// Each of `roots` is kept as well: a CommitID keeps that commit and its
+/// history, an EntryID keeps that entry (for example one appended but not
+/// yet committed). Throws `UNKNOWN_ROOT` for a root that is neither.
+pub fn gc_ledger(ledger: &LedgerContainer, roots: &[String]) -> Result<LedgerContainer> {
+    let mut kept_commits: BTreeSet<String> = get_commit_chain(ledger)?.into_iter().collect();
+    let mut kept_entries: BTreeSet<&String> = BTreeSet::new();
+    for root in roots {
+        if ledger.commits.contains_key(root) {
+            kept_commits.extend(get_history(ledger, root)?);
+        } else if ledger.entries.contains_key(root) {
+            kept_entries.insert(root);
+        } else {
+            return Err(ConcordError::UnknownRoot { root: root.clone() });
+        }
+    }
+    kept_entries.extend(
+        kept_commits
+            .iter()
+            .flat_map(|commit_id| ledger.commits[commit_id].entries.iter()),
+    );
+
+    let mut collected = ledger.clone();
+    collected
+        .commits
+        .retain(|commit_id, _| kept_commits.contains(commit_id));
+    collected
+        .entries
+        .retain(|entry_id, _| kept_entries.contains(entry_id));
+    Ok(collected)
+}
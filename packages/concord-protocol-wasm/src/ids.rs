@@ -0,0 +1,66 @@
+use serde_json::Value;
+
+use crate::canonical::{canonical_stringify, hash_canonical_string};
+use crate::error::Result;
+use crate::types::{Commit, Entry};
+
+pub(crate) fn entry_core_value(entry: &Entry) -> Value {
+    let payload = entry.payload.clone().unwrap_or(Value::Null);
+    serde_json::json!({
+        "kind": entry.kind.clone(),
+        "timestamp": entry.timestamp.clone(),
+        "author": entry.author.clone(),
+        "payload": payload
+    })
+}
+
+/// Canonical signing payload for an entry (excludes signature).
+pub fn get_entry_signing_payload(entry: &Entry) -> Result<String> {
+    canonical_stringify(&entry_core_value(entry))
+}
+
+/// Canonical signing payload bytes for an entry (excludes signature).
+pub fn get_entry_signing_bytes(entry: &Entry) -> Result<Vec<u8>> {
+    Ok(get_entry_signing_payload(entry)?.into_bytes())
+}
+
+/// Deterministically derives an EntryID from entry content.
+pub fn derive_entry_id(entry: &Entry) -> Result<String> {
+    let payload = get_entry_signing_payload(entry)?;
+    Ok(hash_canonical_string(&payload))
+}
+
+pub(crate) fn commit_core_value(commit: &Commit) -> Value {
+    let metadata = commit.metadata.clone().unwrap_or(Value::Null);
+    let mut core = serde_json::json!({
+        "parent": commit.parent.clone(),
+        "timestamp": commit.timestamp.clone(),
+        "metadata": metadata
+    });
+    // A Merkle root stands in for the entry list; validation ties the two.
+    match &commit.entries_root {
+        Some(root) => core["entriesRoot"] = serde_json::json!(root),
+        None => core["entries"] = serde_json::json!(commit.entries.clone()),
+    }
+    // Only merge commits carry the key, so linear CommitIDs are unchanged.
+    if !commit.merge_parents.is_empty() {
+        core["mergeParents"] = serde_json::json!(commit.merge_parents.clone());
+    }
+    core
+}
+
+/// Canonical signing payload for a commit (excludes signature).
+pub fn get_commit_signing_payload(commit: &Commit) -> Result<String> {
+    canonical_stringify(&commit_core_value(commit))
+}
+
+/// Canonical signing payload bytes for a commit (excludes signature).
+pub fn get_commit_signing_bytes(commit: &Commit) -> Result<Vec<u8>> {
+    Ok(get_commit_signing_payload(commit)?.into_bytes())
+}
+
+/// Deterministically derives a CommitID from commit content (excludes signature).
+pub fn derive_commit_id(commit: &Commit) -> Result<String> {
+    let payload = get_commit_signing_payload(commit)?;
+    Ok(hash_canonical_string(&payload))
+}
@@ -0,0 +1,68 @@
+//! The commits listing each entry, carried alongside a container so appends
+//! can look up earlier commits of an entry without walking history.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::types::{Commit, Commits};
+
+/// CommitIDs listing each EntryID.
+///
+/// The index is not serialized. It records how many times `commits` had
+/// been borrowed mutably when it last matched them, and is rebuilt once
+/// that changes, as after deserializing, pruning or editing `commits`
+/// directly. Callers still confirm each hit against the commit itself.
+#[derive(Clone, Default)]
+pub(crate) struct EntryIndex {
+    listed_by: BTreeMap<String, Vec<String>>,
+    edits: Option<u64>,
+}
+
+impl EntryIndex {
+    pub(crate) fn build(commits: &Commits) -> Self {
+        let mut index = EntryIndex::default();
+        for (commit_id, commit) in commits {
+            index.insert(commit_id, commit);
+        }
+        index.matched(commits);
+        index
+    }
+
+    /// True when `commits` has not been borrowed mutably since the index
+    /// last matched it.
+    pub(crate) fn is_current(&self, commits: &Commits) -> bool {
+        self.edits == Some(commits.edits())
+    }
+
+    /// Records that the index matches `commits` as they are now.
+    pub(crate) fn matched(&mut self, commits: &Commits) {
+        self.edits = Some(commits.edits());
+    }
+
+    pub(crate) fn insert(&mut self, commit_id: &str, commit: &Commit) {
+        for entry_id in &commit.entries {
+            let listed_by = self.listed_by.entry(entry_id.clone()).or_default();
+            if listed_by.last().map(String::as_str) != Some(commit_id) {
+                listed_by.push(commit_id.to_string());
+            }
+        }
+    }
+
+    /// CommitIDs that listed `entry_id` when they were indexed.
+    pub(crate) fn listed_by(&self, entry_id: &str) -> &[String] {
+        self.listed_by.get(entry_id).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// The index is derived data, so it never makes two containers differ.
+impl PartialEq for EntryIndex {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl fmt::Debug for EntryIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EntryIndex").finish_non_exhaustive()
+    }
+}
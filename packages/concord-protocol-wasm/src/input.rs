@@ -0,0 +1,175 @@
+use serde_json::{Map, Number, Value};
+
+use crate::diagnostics::ValidationError;
+use crate::error::{ConcordError, Result};
+use crate::types::Entry;
+
+/// A JavaScript value as handed to the protocol, before it is known to be
+/// plain JSON.
+///
+/// `serde_json::Value` cannot represent the inputs `canonicalStringify`
+/// rejects, so host bindings (and conformance tests) convert into this type
+/// first and let [`InputValue::into_json`] apply the same rules.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputValue {
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<InputValue>),
+    Object(Vec<(String, InputValue)>),
+    /// A reference back to an enclosing object or array.
+    Circular,
+    /// A value with no JSON form, named as in the rejection message
+    /// (`function`, `symbol`, `bigint`, `toJSON`).
+    Unsupported(String),
+}
+
+fn canonicalize_error(message: impl Into<String>) -> ConcordError {
+    ConcordError::InvalidJson {
+        message: message.into(),
+    }
+}
+
+impl InputValue {
+    /// Converts to JSON, rejecting the values `canonicalStringify` rejects
+    /// with the same messages.
+    pub fn into_json(self) -> Result<Value> {
+        match self {
+            InputValue::Undefined => Err(canonicalize_error("Cannot canonicalize undefined")),
+            InputValue::Null => Ok(Value::Null),
+            InputValue::Bool(value) => Ok(Value::Bool(value)),
+            InputValue::Number(value) => Number::from_f64(value)
+                .map(Value::Number)
+                .ok_or_else(|| canonicalize_error("Cannot canonicalize non-finite number")),
+            InputValue::String(value) => Ok(Value::String(value)),
+            InputValue::Array(items) => Ok(Value::Array(
+                items
+                    .into_iter()
+                    .map(InputValue::into_json)
+                    .collect::<Result<_>>()?,
+            )),
+            InputValue::Object(fields) => {
+                let mut map = Map::new();
+                for (key, value) in fields {
+                    map.insert(key, value.into_json()?);
+                }
+                Ok(Value::Object(map))
+            }
+            InputValue::Circular => Err(canonicalize_error(
+                "Cannot canonicalize circular references",
+            )),
+            InputValue::Unsupported(kind) => Err(canonicalize_error(format!(
+                "Cannot canonicalize {} values",
+                kind
+            ))),
+        }
+    }
+}
+
+impl From<Value> for InputValue {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => InputValue::Null,
+            Value::Bool(value) => InputValue::Bool(value),
+            Value::Number(number) => InputValue::Number(number.as_f64().unwrap_or(f64::NAN)),
+            Value::String(value) => InputValue::String(value),
+            Value::Array(items) => {
+                InputValue::Array(items.into_iter().map(InputValue::from).collect())
+            }
+            Value::Object(map) => InputValue::Object(
+                map.into_iter()
+                    .map(|(key, value)| (key, InputValue::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+fn invalid_entry(code: &'static str, message: &str, path: Option<&str>) -> ConcordError {
+    let mut error = ValidationError::new(code, message);
+    error.path = path.map(str::to_string);
+    ConcordError::InvalidEntry {
+        message: message.to_string(),
+        errors: vec![error],
+    }
+}
+
+fn string_field(value: InputValue, path: &str, message: &str) -> Result<Option<String>> {
+    match value {
+        InputValue::Undefined | InputValue::Null => Ok(None),
+        InputValue::String(value) => Ok(Some(value)),
+        _ => Err(invalid_entry("INVALID_ENTRY", message, Some(path))),
+    }
+}
+
+impl Entry {
+    /// Builds an entry from a host value, reporting non-canonical payloads as
+    /// `INVALID_ENTRY` like `appendEntry` does.
+    ///
+    /// Missing string fields become empty strings so shape validation can
+    /// report them.
+    pub fn from_input(input: InputValue) -> Result<Entry> {
+        let fields = match input {
+            InputValue::Object(fields) => fields,
+            _ => {
+                return Err(invalid_entry(
+                    "INVALID_ENTRY",
+                    "Entry must be an object",
+                    None,
+                ))
+            }
+        };
+        let mut entry = Entry {
+            kind: String::new(),
+            timestamp: String::new(),
+            author: String::new(),
+            payload: None,
+            signature: None,
+        };
+        for (key, value) in fields {
+            match key.as_str() {
+                "kind" => {
+                    entry.kind =
+                        string_field(value, "kind", "Entry.kind must be a non-empty string")?
+                            .unwrap_or_default()
+                }
+                "timestamp" => {
+                    entry.timestamp = string_field(
+                        value,
+                        "timestamp",
+                        "Entry.timestamp must be a non-empty string",
+                    )?
+                    .unwrap_or_default()
+                }
+                "author" => {
+                    entry.author =
+                        string_field(value, "author", "Entry.author must be a non-empty string")?
+                            .unwrap_or_default()
+                }
+                "signature" => {
+                    entry.signature = string_field(
+                        value,
+                        "signature",
+                        "Entry.signature must be a string or null",
+                    )?
+                }
+                "payload" => {
+                    entry.payload = match value {
+                        InputValue::Undefined | InputValue::Null => None,
+                        value => Some(value.into_json().map_err(|error| {
+                            invalid_entry(
+                                "INVALID_ENTRY_PAYLOAD",
+                                &error.message(),
+                                Some("payload"),
+                            )
+                        })?),
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(entry)
+    }
+}
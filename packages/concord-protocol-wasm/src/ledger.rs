@@ -0,0 +1,560 @@
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::checkpoint::is_pruned_base;
+use crate::error::{ConcordError, Result};
+use crate::ids::{derive_commit_id, derive_entry_id};
+use crate::index::EntryIndex;
+use crate::merkle::compute_entries_root;
+use crate::time::now_iso;
+use crate::types::{
+    Commit, CommitParams, CreatedCommit, Entry, LedgerContainer, ReplaySince, LEDGER_FORMAT,
+    LEDGER_VERSION, PROTOCOL_SPEC,
+};
+use crate::validate::{commit_errors, duplicate_entry_errors, entry_errors, join_messages};
+
+fn build_genesis_metadata(metadata: Option<Value>) -> Result<Value> {
+    let mut merged = Map::new();
+    merged.insert("genesis".to_string(), Value::Bool(true));
+    merged.insert("spec".to_string(), Value::String(PROTOCOL_SPEC.to_string()));
+    match metadata {
+        Some(Value::Object(map)) => {
+            for (key, value) in map {
+                merged.insert(key, value);
+            }
+        }
+        Some(Value::Null) | None => {}
+        Some(_) => {
+            return Err(ConcordError::InvalidMetadata);
+        }
+    }
+    Ok(Value::Object(merged))
+}
+
+/// True when the commit is the Concord genesis commit.
+pub fn is_genesis_commit(commit: &Commit) -> bool {
+    if let Some(Value::Object(meta)) = &commit.metadata {
+        if let Some(Value::Bool(genesis)) = meta.get("genesis") {
+            return *genesis;
+        }
+    }
+    false
+}
+
+/// Creates the genesis commit for a new ledger.
+pub fn create_genesis_commit(
+    metadata: Option<Value>,
+    timestamp: Option<String>,
+) -> Result<CreatedCommit> {
+    let commit = Commit {
+        parent: None,
+        merge_parents: Vec::new(),
+        timestamp: timestamp.unwrap_or_else(now_iso),
+        metadata: Some(build_genesis_metadata(metadata)?),
+        entries: vec![],
+        entries_root: None,
+        signature: None,
+    };
+    let commit_id = derive_commit_id(&commit)?;
+    Ok(CreatedCommit { commit_id, commit })
+}
+
+/// Creates a new ledger container with a genesis commit.
+pub fn create_ledger(
+    metadata: Option<Value>,
+    timestamp: Option<String>,
+) -> Result<LedgerContainer> {
+    let CreatedCommit { commit_id, commit } = create_genesis_commit(metadata, timestamp)?;
+    Ok(LedgerContainer {
+        format: LEDGER_FORMAT.to_string(),
+        version: LEDGER_VERSION.to_string(),
+        commits: std::iter::once((commit_id.clone(), commit)).collect(),
+        entries: BTreeMap::new(),
+        head: commit_id,
+        index: Default::default(),
+    })
+}
+
+/// `parent` followed by any merge parents.
+pub fn get_commit_parents(commit: &Commit) -> Vec<String> {
+    commit
+        .parent
+        .iter()
+        .chain(commit.merge_parents.iter())
+        .cloned()
+        .collect()
+}
+
+/// Ancestors of `commit_id`, itself included, in deterministic replay order.
+///
+/// History stops at genesis or at a checkpoint whose parent was pruned.
+///
+/// Parents are visited depth-first in `parent`, `mergeParents` order and a
+/// commit is emitted once all of its parents have been. A linear history
+/// therefore replays genesis to tip, and a merge replays the history of its
+/// first parent, then whatever each further parent adds, then the merge.
+pub(crate) fn get_history(ledger: &LedgerContainer, commit_id: &str) -> Result<Vec<String>> {
+    let visit = |commit_id: String| -> Result<(String, Vec<String>)> {
+        let commit = match ledger.commits.get(&commit_id) {
+            Some(commit) => commit,
+            None => return Err(ConcordError::MissingCommit { commit_id }),
+        };
+        if commit.parent.as_deref() == Some("")
+            || commit.merge_parents.iter().any(|parent| parent.is_empty())
+        {
+            return Err(ConcordError::InvalidParent {
+                message: "Commit parent must be null or a CommitID".to_string(),
+            });
+        }
+        let mut parents = if is_pruned_base(ledger, commit) {
+            Vec::new()
+        } else {
+            get_commit_parents(commit)
+        };
+        parents.reverse();
+        Ok((commit_id, parents))
+    };
+
+    let mut order: Vec<String> = Vec::new();
+    let mut done: BTreeSet<String> = BTreeSet::new();
+    let mut in_progress: BTreeSet<String> = BTreeSet::new();
+    in_progress.insert(commit_id.to_string());
+    let mut stack = vec![visit(commit_id.to_string())?];
+    while let Some((current, parents)) = stack.last_mut() {
+        match parents.pop() {
+            Some(parent) => {
+                if in_progress.contains(&parent) {
+                    return Err(ConcordError::CommitChainCycle { commit_id: parent });
+                }
+                if !done.contains(&parent) {
+                    in_progress.insert(parent.clone());
+                    stack.push(visit(parent)?);
+                }
+            }
+            None => {
+                let current = current.clone();
+                stack.pop();
+                in_progress.remove(&current);
+                done.insert(current.clone());
+                order.push(current);
+            }
+        }
+    }
+    Ok(order)
+}
+
+/// Returns commit IDs from genesis to head in replay order.
+///
+/// For merged histories this is every ancestor of head: the first-parent
+/// history, then what each merged branch adds, with parents always before
+/// their children.
+pub fn get_commit_chain(ledger: &LedgerContainer) -> Result<Vec<String>> {
+    if !ledger.commits.contains_key(&ledger.head) {
+        return Err(ConcordError::MissingHead {
+            head: ledger.head.clone(),
+        });
+    }
+    get_history(ledger, &ledger.head)
+}
+
+/// The best common ancestor of two commits, or `None` when their histories
+/// are unrelated.
+///
+/// When several common ancestors are equally good (criss-cross merges), the
+/// one latest in `a`'s replay order is chosen.
+pub fn get_merge_base(ledger: &LedgerContainer, a: &str, b: &str) -> Result<Option<String>> {
+    let history_a = get_history(ledger, a)?;
+    let history_b: BTreeSet<String> = get_history(ledger, b)?.into_iter().collect();
+    Ok(history_a
+        .into_iter()
+        .rev()
+        .find(|commit_id| history_b.contains(commit_id)))
+}
+
+/// True when `ancestor` is `commit_id` or one of its ancestors.
+pub fn is_ancestor(ledger: &LedgerContainer, ancestor: &str, commit_id: &str) -> Result<bool> {
+    Ok(get_history(ledger, commit_id)?
+        .iter()
+        .any(|history_id| history_id == ancestor))
+}
+
+/// The container's entry index, rebuilt if it no longer covers `commits`.
+fn entry_index(ledger: &LedgerContainer) -> Cow<'_, EntryIndex> {
+    if ledger.index.is_current(&ledger.commits) {
+        Cow::Borrowed(&ledger.index)
+    } else {
+        Cow::Owned(EntryIndex::build(&ledger.commits))
+    }
+}
+
+fn refresh_index(ledger: &mut LedgerContainer) {
+    if !ledger.index.is_current(&ledger.commits) {
+        ledger.index = EntryIndex::build(&ledger.commits);
+    }
+}
+
+/// True when `target` is one of `from` or among their ancestors. The walk
+/// stops as soon as `target` is found.
+fn reaches(ledger: &LedgerContainer, from: &[String], target: &str) -> bool {
+    let mut seen: BTreeSet<&str> = BTreeSet::new();
+    let mut stack: Vec<&str> = from.iter().map(String::as_str).collect();
+    while let Some(commit_id) = stack.pop() {
+        if commit_id == target {
+            return true;
+        }
+        if !seen.insert(commit_id) {
+            continue;
+        }
+        if let Some(commit) = ledger.commits.get(commit_id) {
+            stack.extend(
+                commit
+                    .parent
+                    .iter()
+                    .chain(commit.merge_parents.iter())
+                    .map(String::as_str),
+            );
+        }
+    }
+    false
+}
+
+/// Fails with `ENTRY_ALREADY_COMMITTED` when one of `entries` is listed by
+/// a commit in the history of `parents`.
+///
+/// Only the commits the index lists for each entry are checked, so history
+/// is walked only for an entry that some commit already lists.
+fn check_not_committed(
+    ledger: &LedgerContainer,
+    index: &EntryIndex,
+    parents: &[String],
+    entries: &[String],
+) -> Result<()> {
+    for entry_id in entries {
+        for commit_id in index.listed_by(entry_id) {
+            let listed = ledger
+                .commits
+                .get(commit_id)
+                .is_some_and(|commit| commit.entries.contains(entry_id));
+            if listed && reaches(ledger, parents, commit_id) {
+                return Err(ConcordError::EntryAlreadyCommitted {
+                    entry_id: entry_id.clone(),
+                    commit_id: commit_id.clone(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Fails with `ENTRY_ALREADY_COMMITTED` when merging `merge_parents` into
+/// `parent` would join branches that each list an entry. Like
+/// `get_merge_base`, this walks the merged histories.
+fn check_merged_branches(
+    ledger: &LedgerContainer,
+    index: &EntryIndex,
+    parent: &str,
+    merge_parents: &[String],
+) -> Result<()> {
+    let mut history: BTreeSet<String> = get_history(ledger, parent)?.into_iter().collect();
+    for merge_parent in merge_parents {
+        for commit_id in get_history(ledger, merge_parent)? {
+            if history.contains(&commit_id) {
+                continue;
+            }
+            for entry_id in &ledger.commits[&commit_id].entries {
+                let first = index.listed_by(entry_id).iter().find(|other| {
+                    history.contains(*other) && ledger.commits[*other].entries.contains(entry_id)
+                });
+                if let Some(first) = first {
+                    return Err(ConcordError::EntryAlreadyCommitted {
+                        entry_id: entry_id.clone(),
+                        commit_id: first.clone(),
+                    });
+                }
+            }
+            history.insert(commit_id);
+        }
+    }
+    Ok(())
+}
+
+/// Creates a non-genesis commit with validated parent and entry references.
+///
+/// With `params.strict`, a commit that would list an entry twice in its
+/// history is refused rather than left for `append_commit` or validation to
+/// reject.
+pub fn create_commit(ledger: &LedgerContainer, params: CommitParams) -> Result<CreatedCommit> {
+    for entry_id in &params.entries {
+        if !ledger.entries.contains_key(entry_id) {
+            return Err(ConcordError::MissingEntry {
+                entry_id: entry_id.clone(),
+            });
+        }
+    }
+
+    let parent = params.parent.unwrap_or_else(|| ledger.head.clone());
+    if parent.is_empty() {
+        return Err(ConcordError::InvalidParent {
+            message: "Non-genesis commits must reference a parent".to_string(),
+        });
+    }
+    for commit_id in std::iter::once(&parent).chain(params.merge_parents.iter()) {
+        if !ledger.commits.contains_key(commit_id) {
+            return Err(ConcordError::MissingCommit {
+                commit_id: commit_id.clone(),
+            });
+        }
+    }
+
+    if params.strict {
+        let errors = duplicate_entry_errors(&params.entries);
+        if !errors.is_empty() {
+            return Err(ConcordError::InvalidCommit {
+                message: join_messages(&errors),
+                errors,
+            });
+        }
+        let index = entry_index(ledger);
+        let parents: Vec<String> = std::iter::once(parent.clone())
+            .chain(params.merge_parents.iter().cloned())
+            .collect();
+        check_merged_branches(ledger, &index, &parent, &params.merge_parents)?;
+        check_not_committed(ledger, &index, &parents, &params.entries)?;
+    }
+
+    let commit = Commit {
+        parent: Some(parent),
+        merge_parents: params.merge_parents,
+        timestamp: params.timestamp.unwrap_or_else(now_iso),
+        metadata: params.metadata.or(Some(Value::Null)),
+        entries_root: if params.entries_root {
+            Some(compute_entries_root(&params.entries))
+        } else {
+            None
+        },
+        entries: params.entries,
+        signature: None,
+    };
+    let commit_id = derive_commit_id(&commit)?;
+    Ok(CreatedCommit { commit_id, commit })
+}
+
+/// Appends a commit to a ledger with validation.
+///
+/// A commit that repeats an EntryID fails with `INVALID_COMMIT`, and one
+/// listing an entry already committed in its history with
+/// `ENTRY_ALREADY_COMMITTED`, naming the earlier commit. The ledger is left
+/// untouched when any check fails.
+pub fn append_commit(
+    ledger: &mut LedgerContainer,
+    commit_id: String,
+    commit: Commit,
+) -> Result<()> {
+    if is_genesis_commit(&commit) {
+        return Err(ConcordError::InvalidCommit {
+            message: "Genesis commits must be created via create_ledger".to_string(),
+            errors: Vec::new(),
+        });
+    }
+    match &commit.parent {
+        Some(parent) if !parent.is_empty() => {}
+        _ => {
+            return Err(ConcordError::InvalidParent {
+                message: "Commit parent must be a non-empty CommitID".to_string(),
+            });
+        }
+    }
+
+    let errors = commit_errors(&commit);
+    if !errors.is_empty() {
+        return Err(ConcordError::InvalidCommit {
+            message: join_messages(&errors),
+            errors,
+        });
+    }
+    for parent in get_commit_parents(&commit) {
+        if !ledger.commits.contains_key(&parent) {
+            return Err(ConcordError::MissingCommit { commit_id: parent });
+        }
+    }
+    for entry_id in &commit.entries {
+        if !ledger.entries.contains_key(entry_id) {
+            return Err(ConcordError::MissingEntry {
+                entry_id: entry_id.clone(),
+            });
+        }
+    }
+    if ledger.commits.contains_key(&commit_id) {
+        return Err(ConcordError::DuplicateCommit { commit_id });
+    }
+    refresh_index(ledger);
+    check_not_committed(
+        ledger,
+        &ledger.index,
+        &get_commit_parents(&commit),
+        &commit.entries,
+    )?;
+
+    ledger.index.insert(&commit_id, &commit);
+    ledger.commits.insert(commit_id.clone(), commit);
+    ledger.index.matched(&ledger.commits);
+    ledger.head = commit_id;
+    Ok(())
+}
+
+/// Append a commit and verify its CommitID matches the commit content.
+pub fn append_commit_strict(
+    ledger: &mut LedgerContainer,
+    commit_id: String,
+    commit: Commit,
+) -> Result<()> {
+    let derived = derive_commit_id(&commit)?;
+    if derived != commit_id {
+        return Err(ConcordError::CommitIdMismatch { commit_id, derived });
+    }
+    append_commit(ledger, commit_id, commit)
+}
+
+/// Append an entry after validating shape and canonicalizability.
+///
+/// Returns the derived EntryID.
+pub fn append_entry(ledger: &mut LedgerContainer, entry: Entry) -> Result<String> {
+    let errors = entry_errors(&entry);
+    if !errors.is_empty() {
+        return Err(ConcordError::InvalidEntry {
+            message: join_messages(&errors),
+            errors,
+        });
+    }
+    let entry_id = derive_entry_id(&entry).map_err(|error| ConcordError::InvalidEntryPayload {
+        message: error.message(),
+    })?;
+    if ledger.entries.contains_key(&entry_id) {
+        return Err(ConcordError::DuplicateEntry { entry_id });
+    }
+    ledger.entries.insert(entry_id.clone(), entry);
+    Ok(entry_id)
+}
+
+fn chain_entry_ids(ledger: &LedgerContainer, chain: &[String]) -> Vec<String> {
+    let mut entry_ids: Vec<String> = Vec::new();
+    for commit_id in chain {
+        if let Some(commit) = ledger.commits.get(commit_id) {
+            if is_genesis_commit(commit) {
+                continue;
+            }
+            entry_ids.extend(commit.entries.iter().cloned());
+        }
+    }
+    entry_ids
+}
+
+fn resolve_entries(ledger: &LedgerContainer, entry_ids: Vec<String>) -> Result<Vec<Entry>> {
+    entry_ids
+        .into_iter()
+        .map(|entry_id| {
+            ledger
+                .entries
+                .get(&entry_id)
+                .cloned()
+                .ok_or(ConcordError::MissingEntry { entry_id })
+        })
+        .collect()
+}
+
+/// Returns entry IDs in deterministic replay order (skipping genesis).
+pub fn get_replay_entry_ids(ledger: &LedgerContainer) -> Result<Vec<String>> {
+    let chain = get_commit_chain(ledger)?;
+    Ok(chain_entry_ids(ledger, &chain))
+}
+
+/// Resolve entries in deterministic replay order.
+pub fn get_replay_entries(ledger: &LedgerContainer) -> Result<Vec<Entry>> {
+    resolve_entries(ledger, get_replay_entry_ids(ledger)?)
+}
+
+/// Resolve the entries committed after `commit_id`, in replay order.
+///
+/// `commit_id` is a cursor from an earlier replay and must be an ancestor of
+/// head. Entries from every commit the cursor does not already cover are
+/// returned, including commits merged in from other branches. The returned
+/// cursor is the current head, to pass to the next call.
+pub fn get_replay_entries_since(ledger: &LedgerContainer, commit_id: &str) -> Result<ReplaySince> {
+    let chain = get_commit_chain(ledger)?;
+    if !chain.iter().any(|chain_id| chain_id == commit_id) {
+        return Err(ConcordError::CursorNotOnChain {
+            commit_id: commit_id.to_string(),
+        });
+    }
+    let seen: BTreeSet<String> = get_history(ledger, commit_id)?.into_iter().collect();
+    let unseen: Vec<String> = chain
+        .into_iter()
+        .filter(|chain_id| !seen.contains(chain_id))
+        .collect();
+    let entries = resolve_entries(ledger, chain_entry_ids(ledger, &unseen))?;
+    Ok(ReplaySince {
+        entries,
+        cursor: ledger.head.clone(),
+    })
+}
+
+/// Reconciles two copies of a ledger that may have diverged.
+///
+/// The result holds the commits and entries of both. When one head already
+/// contains the other, that head is kept (a fast-forward when it is
+/// `theirs`); otherwise a merge commit with parents `[ours.head,
+/// theirs.head]` becomes the new head. Fails with `UNRELATED_HISTORIES` when
+/// the two ledgers share no commit, and with `ENTRY_ALREADY_COMMITTED` when
+/// both sides committed the same entry.
+pub fn merge_ledgers(
+    ours: &LedgerContainer,
+    theirs: &LedgerContainer,
+    metadata: Option<Value>,
+    timestamp: Option<String>,
+) -> Result<LedgerContainer> {
+    get_commit_chain(ours)?;
+    get_commit_chain(theirs)?;
+
+    let mut merged = ours.clone();
+    for (commit_id, commit) in &theirs.commits {
+        merged
+            .commits
+            .entry(commit_id.clone())
+            .or_insert_with(|| commit.clone());
+    }
+    for (entry_id, entry) in &theirs.entries {
+        merged
+            .entries
+            .entry(entry_id.clone())
+            .or_insert_with(|| entry.clone());
+    }
+    refresh_index(&mut merged);
+
+    match get_merge_base(&merged, &ours.head, &theirs.head)? {
+        None => {
+            return Err(ConcordError::UnrelatedHistories {
+                ours: ours.head.clone(),
+                theirs: theirs.head.clone(),
+            });
+        }
+        Some(base) if base == theirs.head => {}
+        Some(base) if base == ours.head => merged.head = theirs.head.clone(),
+        Some(_) => {
+            let created = create_commit(
+                &merged,
+                CommitParams {
+                    entries: Vec::new(),
+                    metadata,
+                    timestamp,
+                    parent: Some(ours.head.clone()),
+                    merge_parents: vec![theirs.head.clone()],
+                    entries_root: false,
+                    strict: true,
+                },
+            )?;
+            append_commit(&mut merged, created.commit_id, created.commit)?;
+        }
+    }
+    Ok(merged)
+}
@@ -0,0 +1,183 @@
+//! Merkle roots over a commit's EntryIDs and per-entry inclusion proofs.
+//!
+//! The tree has the shape of RFC 6962 §2.1: the left subtree holds the
+//! largest power of two leaves, a leaf hash is `SHA-256(0x00 || EntryID)`
+//! and an interior hash is `SHA-256(0x01 || left || right)`, with EntryIDs
+//! as UTF-8. An empty commit has the root `SHA-256("")`.
+
+use sha2::{Digest, Sha256};
+
+use crate::error::{ConcordError, Result};
+use crate::ids::{derive_commit_id, derive_entry_id};
+use crate::types::{EntryInclusionProof, LedgerContainer};
+use crate::validate::{commit_errors, join_messages};
+
+type Hash = [u8; 32];
+
+fn leaf_hash(entry_id: &str) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([0x00]);
+    hasher.update(entry_id.as_bytes());
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &Hash, right: &Hash) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([0x01]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// The largest power of two below `n` (for `n > 1`).
+fn split(n: usize) -> usize {
+    let mut k = 1;
+    while k * 2 < n {
+        k *= 2;
+    }
+    k
+}
+
+fn tree_hash(leaves: &[Hash]) -> Hash {
+    match leaves.len() {
+        0 => Sha256::digest([]).into(),
+        1 => leaves[0],
+        n => {
+            let k = split(n);
+            node_hash(&tree_hash(&leaves[..k]), &tree_hash(&leaves[k..]))
+        }
+    }
+}
+
+fn audit_path(index: usize, leaves: &[Hash], path: &mut Vec<Hash>) {
+    let n = leaves.len();
+    if n <= 1 {
+        return;
+    }
+    let k = split(n);
+    if index < k {
+        audit_path(index, &leaves[..k], path);
+        path.push(tree_hash(&leaves[k..]));
+    } else {
+        audit_path(index - k, &leaves[k..], path);
+        path.push(tree_hash(&leaves[..k]));
+    }
+}
+
+fn to_hex(hash: &Hash) -> String {
+    hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Hash> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        return None;
+    }
+    let mut hash = [0u8; 32];
+    for (index, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).ok()?;
+    }
+    Some(hash)
+}
+
+/// The Merkle root of `entry_ids`, in order, as lowercase hex.
+pub fn compute_entries_root(entry_ids: &[String]) -> String {
+    let leaves: Vec<Hash> = entry_ids.iter().map(|id| leaf_hash(id)).collect();
+    to_hex(&tree_hash(&leaves))
+}
+
+/// Proves that `entry_id` is listed in a commit that records an
+/// `entriesRoot`.
+///
+/// The proof carries the commit without its `entries`, which is enough to
+/// re-derive the CommitID and check the commit seal.
+pub fn prove_entry_inclusion(
+    ledger: &LedgerContainer,
+    commit_id: &str,
+    entry_id: &str,
+) -> Result<EntryInclusionProof> {
+    let commit = ledger
+        .commits
+        .get(commit_id)
+        .ok_or_else(|| ConcordError::MissingCommit {
+            commit_id: commit_id.to_string(),
+        })?;
+    if commit.entries_root.is_none() {
+        return Err(ConcordError::MissingEntriesRoot {
+            commit_id: commit_id.to_string(),
+        });
+    }
+    let errors = commit_errors(commit);
+    if !errors.is_empty() {
+        return Err(ConcordError::InvalidCommit {
+            message: join_messages(&errors),
+            errors,
+        });
+    }
+    let index = commit
+        .entries
+        .iter()
+        .position(|id| id == entry_id)
+        .ok_or_else(|| ConcordError::EntryNotInCommit {
+            entry_id: entry_id.to_string(),
+            commit_id: commit_id.to_string(),
+        })?;
+
+    let leaves: Vec<Hash> = commit.entries.iter().map(|id| leaf_hash(id)).collect();
+    let mut path = Vec::new();
+    audit_path(index, &leaves, &mut path);
+    let mut header = commit.clone();
+    header.entries = Vec::new();
+    Ok(EntryInclusionProof {
+        entry_id: entry_id.to_string(),
+        commit_id: commit_id.to_string(),
+        commit: header,
+        index,
+        leaf_count: leaves.len(),
+        path: path.iter().map(to_hex).collect(),
+    })
+}
+
+/// Checks an inclusion proof against the entry it is for.
+///
+/// True when the entry derives `proof.entryId`, the proof commit derives
+/// `proof.commitId`, and the audit path leads from the entry to the
+/// commit's `entriesRoot`. Checking the commit seal, or that the CommitID is
+/// on a trusted chain, is left to the caller.
+pub fn verify_entry_inclusion(entry: &crate::types::Entry, proof: &EntryInclusionProof) -> bool {
+    let root = match proof.commit.entries_root.as_deref().and_then(from_hex) {
+        Some(root) => root,
+        None => return false,
+    };
+    if derive_entry_id(entry).ok().as_deref() != Some(proof.entry_id.as_str())
+        || derive_commit_id(&proof.commit).ok().as_deref() != Some(proof.commit_id.as_str())
+        || proof.index >= proof.leaf_count
+    {
+        return false;
+    }
+
+    // RFC 9162 §2.1.3.2.
+    let mut index = proof.index;
+    let mut last = proof.leaf_count - 1;
+    let mut hash = leaf_hash(&proof.entry_id);
+    for sibling in &proof.path {
+        let sibling = match from_hex(sibling) {
+            Some(sibling) => sibling,
+            None => return false,
+        };
+        if last == 0 {
+            return false;
+        }
+        if index & 1 == 1 || index == last {
+            hash = node_hash(&sibling, &hash);
+            while index & 1 == 0 && index != 0 {
+                index >>= 1;
+                last >>= 1;
+            }
+        } else {
+            hash = node_hash(&hash, &sibling);
+        }
+        index >>= 1;
+        last >>= 1;
+    }
+    last == 0 && hash == root
+}
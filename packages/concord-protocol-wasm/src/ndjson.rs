@@ -0,0 +1,283 @@
+//! Line-delimited (NDJSON) export and streaming import of ledgers.
+//!
+//! Each line is one JSON record with a `type`:
+//!
+//! - `{"type":"header","format","version","head"}` comes first, once.
+//! - `{"type":"entry","entryId","entry"}` for each entry, just before the
+//!   first commit that references it.
+//! - `{"type":"commit","commitId","commit"}` for each commit in replay order,
+//!   genesis (or the checkpoint of a pruned ledger) first and head last.
+//!
+//! Entries that no commit references follow the last commit, in ID order.
+//! Commits that are not reachable from head are not exported.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use crate::checkpoint::{checkpoint_errors, is_checkpoint_commit};
+use crate::error::{ConcordError, Result};
+use crate::ids::derive_commit_id;
+use crate::ledger::{append_commit_strict, append_entry, get_commit_chain, get_history};
+use crate::types::{Commit, Entry, LedgerContainer};
+use crate::validate::{commit_errors, genesis_errors, join_messages};
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum RecordRef<'a> {
+    Header {
+        format: &'a str,
+        version: &'a str,
+        head: &'a str,
+    },
+    Entry {
+        #[serde(rename = "entryId")]
+        entry_id: &'a str,
+        entry: &'a Entry,
+    },
+    Commit {
+        #[serde(rename = "commitId")]
+        commit_id: &'a str,
+        commit: &'a Commit,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Record {
+    Header {
+        format: String,
+        version: String,
+        head: String,
+    },
+    Entry {
+        #[serde(rename = "entryId")]
+        entry_id: String,
+        entry: Entry,
+    },
+    Commit {
+        #[serde(rename = "commitId")]
+        commit_id: String,
+        commit: Commit,
+    },
+}
+
+/// The NDJSON lines of a ledger, without trailing newlines.
+///
+/// Lines are serialized one at a time as the iterator is advanced.
+pub fn ledger_ndjson_lines(ledger: &LedgerContainer) -> Result<impl Iterator<Item = String> + '_> {
+    let chain = get_commit_chain(ledger)?;
+    let mut records = vec![RecordRef::Header {
+        format: &ledger.format,
+        version: &ledger.version,
+        head: &ledger.head,
+    }];
+    let mut emitted = BTreeSet::new();
+    for commit_id in &chain {
+        let (commit_id, commit) = ledger.commits.get_key_value(commit_id).unwrap();
+        for entry_id in &commit.entries {
+            let (entry_id, entry) = ledger.entries.get_key_value(entry_id).ok_or_else(|| {
+                ConcordError::MissingEntry {
+                    entry_id: entry_id.clone(),
+                }
+            })?;
+            if emitted.insert(entry_id) {
+                records.push(RecordRef::Entry { entry_id, entry });
+            }
+        }
+        records.push(RecordRef::Commit { commit_id, commit });
+    }
+    for (entry_id, entry) in &ledger.entries {
+        if !emitted.contains(entry_id) {
+            records.push(RecordRef::Entry { entry_id, entry });
+        }
+    }
+    Ok(records
+        .into_iter()
+        .map(|record| serde_json::to_string(&record).expect("ledger records serialize to JSON")))
+}
+
+/// The whole NDJSON export as one string, one record per line.
+pub fn export_ledger_ndjson(ledger: &LedgerContainer) -> Result<String> {
+    let mut out = String::new();
+    for line in ledger_ndjson_lines(ledger)? {
+        out.push_str(&line);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Builds a ledger from NDJSON records as they arrive.
+///
+/// Every record is checked when its line completes: the header must come
+/// first, the first commit must be a well-formed genesis or the expected
+/// pruned base, IDs are re-derived, and each commit must follow its parents
+/// and entries. Only the ledger built so far and one partial line are held
+/// in memory. After an error the reader should be discarded.
+#[derive(Debug, Default)]
+pub struct NdjsonReader {
+    ledger: Option<LedgerContainer>,
+    head: String,
+    pending: String,
+    line: usize,
+    pruned_base: Option<String>,
+}
+
+impl NdjsonReader {
+    /// A reader for a ledger that starts at its genesis commit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A reader for a pruned ledger whose first commit is the checkpoint
+    /// `pruned_base`, as `prune_ledger` leaves it.
+    pub fn with_pruned_base(pruned_base: impl Into<String>) -> Self {
+        Self {
+            pruned_base: Some(pruned_base.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Lines read so far, including blank ones.
+    pub fn lines_read(&self) -> usize {
+        self.line
+    }
+
+    /// Feeds a chunk of text, which may end mid-line.
+    pub fn push(&mut self, chunk: &str) -> Result<()> {
+        self.pending.push_str(chunk);
+        let complete = match self.pending.rfind('\n') {
+            Some(index) => index + 1,
+            None => return Ok(()),
+        };
+        let rest = self.pending.split_off(complete);
+        let lines = std::mem::replace(&mut self.pending, rest);
+        for line in lines.lines() {
+            self.push_line(line)?;
+        }
+        Ok(())
+    }
+
+    /// Reads one complete line. Blank lines are skipped.
+    ///
+    /// Errors other than `INVALID_NDJSON` keep their code and gain the
+    /// 1-based `line` in their context.
+    pub fn push_line(&mut self, line: &str) -> Result<()> {
+        self.line += 1;
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            return Ok(());
+        }
+        self.read_record(line).map_err(|error| match error {
+            ConcordError::InvalidNdjson { .. } => error,
+            error => ConcordError::AtNdjsonLine {
+                line: self.line,
+                error: Box::new(error),
+            },
+        })
+    }
+
+    fn read_record(&mut self, line: &str) -> Result<()> {
+        let record: Record = serde_json::from_str(line).map_err(|error| self.invalid(error))?;
+        match (record, &mut self.ledger) {
+            (
+                Record::Header {
+                    format,
+                    version,
+                    head,
+                },
+                None,
+            ) => {
+                self.head = head;
+                self.ledger = Some(LedgerContainer {
+                    format,
+                    version,
+                    head: String::new(),
+                    commits: Default::default(),
+                    entries: Default::default(),
+                    index: Default::default(),
+                });
+                Ok(())
+            }
+            (Record::Header { .. }, Some(_)) => Err(self.invalid("Duplicate header")),
+            (_, None) => Err(self.invalid("The first record must be the header")),
+            (Record::Entry { entry_id, entry }, Some(ledger)) => {
+                let derived = append_entry(ledger, entry)?;
+                if derived != entry_id {
+                    ledger.entries.remove(&derived);
+                    return Err(ConcordError::EntryIdMismatch { entry_id, derived });
+                }
+                Ok(())
+            }
+            (Record::Commit { commit_id, commit }, Some(ledger)) if ledger.commits.is_empty() => {
+                // Genesis, checked as validate_ledger checks it, or the
+                // checkpoint the reader was told a pruned ledger starts from.
+                let mut errors = commit_errors(&commit);
+                if commit.parent.is_none() {
+                    errors.extend(genesis_errors(&commit, true));
+                } else if self.pruned_base.as_ref() != Some(&commit_id) {
+                    return Err(self.invalid(format!(
+                        "The first commit {} has a parent but is not the expected pruned base",
+                        commit_id
+                    )));
+                } else if is_checkpoint_commit(&commit) && commit.entries.is_empty() {
+                    errors.extend(checkpoint_errors(&commit));
+                } else {
+                    return Err(self.invalid("The pruned base must be a checkpoint"));
+                }
+                if !errors.is_empty() {
+                    let errors: Vec<_> = errors
+                        .into_iter()
+                        .map(|error| error.with_commit(&commit_id))
+                        .collect();
+                    return Err(ConcordError::InvalidCommit {
+                        message: join_messages(&errors),
+                        errors,
+                    });
+                }
+                let derived = derive_commit_id(&commit)?;
+                if derived != commit_id {
+                    return Err(ConcordError::CommitIdMismatch { commit_id, derived });
+                }
+                ledger.commits.insert(commit_id.clone(), commit);
+                ledger.head = commit_id;
+                Ok(())
+            }
+            (Record::Commit { commit_id, commit }, Some(ledger)) => {
+                append_commit_strict(ledger, commit_id, commit)
+            }
+        }
+    }
+
+    /// Reads any final unterminated line and returns the ledger.
+    ///
+    /// Fails unless the stream ended on the header's head and every commit
+    /// is reachable from it.
+    pub fn finish(mut self) -> Result<LedgerContainer> {
+        if !self.pending.is_empty() {
+            let line = std::mem::take(&mut self.pending);
+            self.push_line(&line)?;
+        }
+        let ledger = match self.ledger.take() {
+            Some(ledger) => ledger,
+            None => return Err(self.invalid("Missing header")),
+        };
+        if ledger.head != self.head {
+            return Err(self.invalid(format!(
+                "Stream ends at commit {} but the header head is {}",
+                ledger.head, self.head
+            )));
+        }
+        let history: BTreeSet<String> = get_history(&ledger, &ledger.head)?.into_iter().collect();
+        if let Some(commit_id) = ledger.commits.keys().find(|id| !history.contains(*id)) {
+            return Err(self.invalid(format!("Commit {} is not reachable from head", commit_id)));
+        }
+        Ok(ledger)
+    }
+
+    fn invalid(&self, message: impl ToString) -> ConcordError {
+        ConcordError::InvalidNdjson {
+            line: self.line,
+            message: message.to_string(),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Light-client proofs that an entry is in the history of a head.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::error::{ConcordError, Result};
+use crate::ids::{derive_commit_id, derive_entry_id};
+use crate::ledger::{get_commit_chain, get_commit_parents};
+use crate::merkle::compute_entries_root;
+use crate::types::{CreatedCommit, Entry, LedgerContainer, LedgerInclusionProof};
+
+/// Proves that `entry_id` is committed in the history of the ledger head.
+///
+/// The proof is the shortest run of parent links from head back to the
+/// nearest commit listing the entry. Commits along the way that record an
+/// `entriesRoot` are sent without their `entries`, since the root alone
+/// derives their CommitID. Throws `MISSING_ENTRY` when no commit reachable
+/// from head lists the entry.
+pub fn prove_entry_in_ledger(
+    ledger: &LedgerContainer,
+    entry_id: &str,
+) -> Result<LedgerInclusionProof> {
+    // Rejects missing commits and cycles up front.
+    get_commit_chain(ledger)?;
+
+    let mut children: BTreeMap<&str, &str> = BTreeMap::new();
+    let mut queue = VecDeque::from(vec![ledger.head.as_str()]);
+    let mut found = None;
+    while let Some(commit_id) = queue.pop_front() {
+        let (commit_id, commit) = ledger.commits.get_key_value(commit_id).unwrap();
+        if commit.entries.iter().any(|id| id == entry_id) {
+            found = Some(commit_id.as_str());
+            break;
+        }
+        for parent in commit.parent.iter().chain(commit.merge_parents.iter()) {
+            // A pruned base's parent is absent; history stops there.
+            if parent != &ledger.head
+                && !children.contains_key(parent.as_str())
+                && ledger.commits.contains_key(parent)
+            {
+                children.insert(parent, commit_id);
+                queue.push_back(parent);
+            }
+        }
+    }
+    let mut current = found.ok_or_else(|| ConcordError::MissingEntry {
+        entry_id: entry_id.to_string(),
+    })?;
+
+    let mut path = vec![current];
+    while let Some(child) = children.get(current) {
+        current = child;
+        path.push(current);
+    }
+    let last = path.len() - 1;
+    let commits = path
+        .into_iter()
+        .rev()
+        .enumerate()
+        .map(|(index, commit_id)| {
+            let mut commit = ledger.commits[commit_id].clone();
+            if index != last && commit.entries_root.is_some() {
+                commit.entries = Vec::new();
+            }
+            CreatedCommit {
+                commit_id: commit_id.to_string(),
+                commit,
+            }
+        })
+        .collect();
+    Ok(LedgerInclusionProof {
+        entry_id: entry_id.to_string(),
+        commits,
+    })
+}
+
+/// Checks a [`prove_entry_in_ledger`] proof against a trusted head.
+///
+/// True when `entry` derives `proof.entryId`, the first commit is `head`,
+/// every commit derives its CommitID and names the next as a parent, and
+/// the last commit lists the entry (matching its `entriesRoot`, if any).
+pub fn verify_entry_in_ledger(entry: &Entry, proof: &LedgerInclusionProof, head: &str) -> bool {
+    if derive_entry_id(entry).ok().as_deref() != Some(proof.entry_id.as_str()) {
+        return false;
+    }
+    let last = match proof.commits.last() {
+        Some(last) => &last.commit,
+        None => return false,
+    };
+    if proof.commits[0].commit_id != head {
+        return false;
+    }
+    let derives = proof.commits.iter().all(|created| {
+        derive_commit_id(&created.commit).ok().as_deref() == Some(created.commit_id.as_str())
+    });
+    let linked = proof
+        .commits
+        .windows(2)
+        .all(|pair| get_commit_parents(&pair[0].commit).contains(&pair[1].commit_id));
+    let listed = last.entries.contains(&proof.entry_id)
+        && last
+            .entries_root
+            .as_ref()
+            .is_none_or(|root| *root == compute_entries_root(&last.entries));
+    derives && linked && listed
+}
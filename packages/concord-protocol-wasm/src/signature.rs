@@ -0,0 +1,77 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use std::convert::TryInto;
+
+use crate::error::{ConcordError, Result};
+use crate::ids::get_entry_signing_bytes;
+use crate::types::Entry;
+
+/// Domain separator prepended (with a NUL byte) to every signed payload, as
+/// in `@ternent/identity` `signBytes`.
+pub const SIGNATURE_CONTEXT: &str = "ternent-seal/v2";
+
+/// Decodes base64url the way `@ternent/identity` does: surrounding whitespace,
+/// standard-alphabet characters and padding are tolerated.
+fn decode_base64url(value: &str) -> Option<Vec<u8>> {
+    let normalized = value
+        .trim()
+        .replace('+', "-")
+        .replace('/', "_")
+        .trim_end_matches('=')
+        .to_string();
+    if normalized.is_empty() {
+        return None;
+    }
+    URL_SAFE_NO_PAD.decode(normalized).ok()
+}
+
+/// Verifies an Ed25519 `signature` over `payload` under the identity
+/// signing context. Keys and signatures are base64url, as produced by
+/// `@ternent/identity`.
+pub fn verify_ed25519(public_key: &str, payload: &[u8], signature: &str) -> Result<bool> {
+    let public_key: [u8; 32] = decode_base64url(public_key)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| ConcordError::InvalidPublicKey {
+            message: "Public key must be 32 base64url bytes".to_string(),
+        })?;
+    let public_key =
+        VerifyingKey::from_bytes(&public_key).map_err(|_| ConcordError::InvalidPublicKey {
+            message: "Public key is not a valid Ed25519 point".to_string(),
+        })?;
+    let signature: [u8; 64] = decode_base64url(signature)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| ConcordError::InvalidSignature {
+            message: "Signature must be 64 base64url bytes".to_string(),
+        })?;
+
+    let mut message = Vec::with_capacity(SIGNATURE_CONTEXT.len() + 1 + payload.len());
+    message.extend_from_slice(SIGNATURE_CONTEXT.as_bytes());
+    message.push(0);
+    message.extend_from_slice(payload);
+    Ok(public_key
+        .verify(&message, &Signature::from_bytes(&signature))
+        .is_ok())
+}
+
+/// Checks `entry.signature` against the entry signing bytes, treating
+/// `entry.author` as the signer's Ed25519 public key.
+///
+/// Fails with `ENTRY_SIGNATURE_MISSING` when the entry is unsigned and
+/// `ENTRY_SIGNATURE_INVALID` when the signature, key, or payload do not check
+/// out.
+pub fn verify_entry_signature(entry: &Entry) -> Result<()> {
+    let signature = match entry.signature.as_deref() {
+        Some(signature) if !signature.is_empty() => signature,
+        _ => return Err(ConcordError::EntrySignatureMissing),
+    };
+    let invalid = |message: String| ConcordError::EntrySignatureInvalid { message };
+    let payload = get_entry_signing_bytes(entry).map_err(|error| invalid(error.message()))?;
+    match verify_ed25519(&entry.author, &payload, signature) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(invalid(
+            "Entry.signature does not verify against Entry.author".to_string(),
+        )),
+        Err(error) => Err(invalid(error.message())),
+    }
+}
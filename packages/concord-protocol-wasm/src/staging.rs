@@ -0,0 +1,178 @@
+//! Staged versus committed truth.
+//!
+//! [`StagedLedger`] pairs a ledger container with a staging area. Entries are
+//! staged in order and only reach the container when [`StagedLedger::commit`]
+//! groups them into a commit; the container itself never holds staged state,
+//! so it can be exported at any time.
+
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+use crate::error::{ConcordError, Result};
+use crate::ids::derive_entry_id;
+use crate::ledger::{append_commit, create_commit, get_replay_entries};
+use crate::types::{CommitParams, Entry, LedgerContainer, StagedCommit, StagedEntry};
+use crate::validate::{entry_errors, join_messages};
+
+/// A ledger container with entries staged for the next commit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StagedLedger {
+    ledger: LedgerContainer,
+    staged: Vec<StagedEntry>,
+}
+
+fn staged_entry(entry: Entry) -> Result<StagedEntry> {
+    let errors = entry_errors(&entry);
+    if !errors.is_empty() {
+        return Err(ConcordError::InvalidEntry {
+            message: join_messages(&errors),
+            errors,
+        });
+    }
+    let entry_id = derive_entry_id(&entry).map_err(|error| ConcordError::InvalidEntryPayload {
+        message: error.message(),
+    })?;
+    Ok(StagedEntry { entry_id, entry })
+}
+
+impl StagedLedger {
+    /// Wraps a container with an empty staging area.
+    pub fn new(ledger: LedgerContainer) -> Self {
+        StagedLedger {
+            ledger,
+            staged: Vec::new(),
+        }
+    }
+
+    /// The committed container.
+    pub fn ledger(&self) -> &LedgerContainer {
+        &self.ledger
+    }
+
+    /// Mutable access to the committed container, for appending commits
+    /// received from elsewhere. Staged entries are untouched.
+    pub fn ledger_mut(&mut self) -> &mut LedgerContainer {
+        &mut self.ledger
+    }
+
+    /// Drops the staging area and returns the committed container.
+    pub fn into_ledger(self) -> LedgerContainer {
+        self.ledger
+    }
+
+    /// Staged entries in staging order.
+    pub fn staged(&self) -> &[StagedEntry] {
+        &self.staged
+    }
+
+    /// Stages an entry after validating shape and canonicalizability.
+    ///
+    /// Returns the derived EntryID.
+    pub fn stage(&mut self, entry: Entry) -> Result<String> {
+        let mut entry_ids = self.stage_many(vec![entry])?;
+        Ok(entry_ids.remove(0))
+    }
+
+    /// Stages entries in order. Nothing is staged when any entry is invalid
+    /// or already committed or staged.
+    pub fn stage_many(&mut self, entries: Vec<Entry>) -> Result<Vec<String>> {
+        let mut seen: BTreeSet<String> = self
+            .staged
+            .iter()
+            .map(|staged| staged.entry_id.clone())
+            .collect();
+        let mut batch: Vec<StagedEntry> = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let staged = staged_entry(entry)?;
+            if self.ledger.entries.contains_key(&staged.entry_id)
+                || !seen.insert(staged.entry_id.clone())
+            {
+                return Err(ConcordError::DuplicateEntry {
+                    entry_id: staged.entry_id,
+                });
+            }
+            batch.push(staged);
+        }
+        let entry_ids = batch.iter().map(|staged| staged.entry_id.clone()).collect();
+        self.staged.extend(batch);
+        Ok(entry_ids)
+    }
+
+    /// Discards every staged entry.
+    pub fn clear_staged(&mut self) {
+        self.staged.clear();
+    }
+
+    /// Commits the staged entries, in staging order, on top of the head.
+    ///
+    /// Fails with `NOTHING_STAGED` when the staging area is empty. On any
+    /// failure both the container and the staging area are left untouched;
+    /// on success the staging area is cleared.
+    pub fn commit(
+        &mut self,
+        metadata: Option<Value>,
+        timestamp: Option<String>,
+    ) -> Result<StagedCommit> {
+        if self.staged.is_empty() {
+            return Err(ConcordError::NothingStaged);
+        }
+        for staged in &self.staged {
+            if self.ledger.entries.contains_key(&staged.entry_id) {
+                return Err(ConcordError::DuplicateEntry {
+                    entry_id: staged.entry_id.clone(),
+                });
+            }
+        }
+
+        let entry_ids: Vec<String> = self
+            .staged
+            .iter()
+            .map(|staged| staged.entry_id.clone())
+            .collect();
+        for staged in &self.staged {
+            self.ledger
+                .entries
+                .insert(staged.entry_id.clone(), staged.entry.clone());
+        }
+        let params = CommitParams {
+            entries: entry_ids,
+            metadata,
+            timestamp,
+            parent: None,
+            merge_parents: Vec::new(),
+            entries_root: false,
+            strict: false,
+        };
+        let appended = create_commit(&self.ledger, params).and_then(|created| {
+            append_commit(
+                &mut self.ledger,
+                created.commit_id.clone(),
+                created.commit.clone(),
+            )?;
+            Ok(created)
+        });
+        match appended {
+            Ok(created) => Ok(StagedCommit {
+                commit_id: created.commit_id,
+                commit: created.commit,
+                entries: std::mem::take(&mut self.staged),
+            }),
+            Err(error) => {
+                for staged in &self.staged {
+                    self.ledger.entries.remove(&staged.entry_id);
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Resolve entries in deterministic replay order, followed by the staged
+    /// entries when `include_staged` is set.
+    pub fn get_replay_entries(&self, include_staged: bool) -> Result<Vec<Entry>> {
+        let mut entries = get_replay_entries(&self.ledger)?;
+        if include_staged {
+            entries.extend(self.staged.iter().map(|staged| staged.entry.clone()));
+        }
+        Ok(entries)
+    }
+}
@@ -0,0 +1,26 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// Current time as an ISO 8601 string, matching `Date.prototype.toISOString`.
+pub(crate) fn now_iso() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Milliseconds since the Unix epoch for an RFC 3339 timestamp, or `None`
+/// when it is not one.
+pub(crate) fn parse_timestamp_millis(value: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|time| time.timestamp_millis())
+}
+
+/// True when `time` is strictly before `other`, both read as RFC 3339.
+///
+/// `Date.parse` also reads date-only and offset-less forms, the latter in
+/// the host's local time zone; those never compare as out of order here,
+/// nor does anything else that is not RFC 3339.
+pub(crate) fn is_before(time: &str, other: &str) -> bool {
+    match (parse_timestamp_millis(time), parse_timestamp_millis(other)) {
+        (Some(time), Some(other)) => time < other,
+        _ => false,
+    }
+}
@@ -0,0 +1,348 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{Deref, DerefMut};
+
+use crate::diagnostics::ValidationError;
+use crate::index::EntryIndex;
+
+pub const PROTOCOL_SPEC: &str = "concord-protocol@1.0";
+pub const LEDGER_FORMAT: &str = "concord-ledger";
+pub const LEDGER_VERSION: &str = "1.0";
+pub const BUNDLE_FORMAT: &str = "concord-bundle";
+pub const BUNDLE_VERSION: &str = "1.0";
+
+/// Canonical entry payload for Concord.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub kind: String,
+    pub timestamp: String,
+    pub author: String,
+    pub payload: Option<Value>,
+    pub signature: Option<String>,
+}
+
+/// Canonical commit payload for Concord.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Commit {
+    pub parent: Option<String>,
+    /// Further parents of a merge commit, after `parent`. Omitted from the
+    /// wire format and the CommitID when empty.
+    #[serde(
+        default,
+        rename = "mergeParents",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub merge_parents: Vec<String>,
+    pub timestamp: String,
+    pub metadata: Option<Value>,
+    pub entries: Vec<String>,
+    /// Merkle root of `entries` (see `compute_entries_root`). When present,
+    /// the CommitID covers the root in place of the `entries` list, so a
+    /// single entry can be proven against the commit.
+    #[serde(
+        default,
+        rename = "entriesRoot",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub entries_root: Option<String>,
+    /// Commit seal: a signature over the commit signing bytes. It is never
+    /// part of the CommitID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+/// A ledger's commits by CommitID.
+///
+/// Derefs to the map. Every mutable borrow counts as an edit, so the
+/// container's entry index is rebuilt before it is next used.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(transparent)]
+pub struct Commits {
+    map: BTreeMap<String, Commit>,
+    #[serde(skip)]
+    edits: u64,
+}
+
+impl Commits {
+    /// Mutable borrows taken so far.
+    pub(crate) fn edits(&self) -> u64 {
+        self.edits
+    }
+}
+
+impl Deref for Commits {
+    type Target = BTreeMap<String, Commit>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+impl DerefMut for Commits {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.edits += 1;
+        &mut self.map
+    }
+}
+
+impl PartialEq for Commits {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl fmt::Debug for Commits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+impl From<BTreeMap<String, Commit>> for Commits {
+    fn from(map: BTreeMap<String, Commit>) -> Self {
+        Commits { map, edits: 0 }
+    }
+}
+
+impl FromIterator<(String, Commit)> for Commits {
+    fn from_iter<I: IntoIterator<Item = (String, Commit)>>(iter: I) -> Self {
+        BTreeMap::from_iter(iter).into()
+    }
+}
+
+impl IntoIterator for Commits {
+    type Item = (String, Commit);
+    type IntoIter = std::collections::btree_map::IntoIter<String, Commit>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Commits {
+    type Item = (&'a String, &'a Commit);
+    type IntoIter = std::collections::btree_map::Iter<'a, String, Commit>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.iter()
+    }
+}
+
+/// Canonical Concord ledger container.
+///
+/// Build one with [`create_ledger`](crate::create_ledger) or by
+/// deserializing; it also carries an index that is not serialized.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LedgerContainer {
+    pub format: String,
+    pub version: String,
+    pub commits: Commits,
+    pub entries: BTreeMap<String, Entry>,
+    pub head: String,
+    #[serde(skip)]
+    pub(crate) index: EntryIndex,
+}
+
+/// Parameters for creating a non-genesis commit.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct CommitParams {
+    pub entries: Vec<String>,
+    pub metadata: Option<Value>,
+    pub timestamp: Option<String>,
+    pub parent: Option<String>,
+    /// Makes the commit a merge of `parent` (or head) and these commits.
+    #[serde(default, rename = "mergeParents")]
+    pub merge_parents: Vec<String>,
+    /// Records the Merkle root of `entries` in `Commit.entriesRoot`.
+    #[serde(default, rename = "entriesRoot")]
+    pub entries_root: bool,
+    /// Refuses to create a commit whose history would list an entry twice,
+    /// including a merge of branches that each committed it.
+    #[serde(default)]
+    pub strict: bool,
+}
+
+/// A commit together with its derived CommitID.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CreatedCommit {
+    #[serde(rename = "commitId")]
+    pub commit_id: String,
+    pub commit: Commit,
+}
+
+/// Proof that an entry is listed in a commit with an `entriesRoot`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct EntryInclusionProof {
+    #[serde(rename = "entryId")]
+    pub entry_id: String,
+    #[serde(rename = "commitId")]
+    pub commit_id: String,
+    /// The commit with `entries` left empty. Its other fields, including
+    /// `entriesRoot`, still derive `commitId`.
+    pub commit: Commit,
+    /// Position of the entry in the commit's `entries`.
+    pub index: usize,
+    /// Number of entries in the commit.
+    #[serde(rename = "leafCount")]
+    pub leaf_count: usize,
+    /// Sibling hashes from the leaf up to the root, as hex.
+    pub path: Vec<String>,
+}
+
+/// The `metadata["concord/checkpoint"]` record of a checkpoint commit.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Checkpoint {
+    /// Number of commits before the checkpoint (genesis has height 0).
+    pub height: u64,
+    /// Hash of the projection state after replaying every earlier entry.
+    #[serde(rename = "stateHash")]
+    pub state_hash: String,
+}
+
+/// Proof that an entry is committed in the history of a head.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LedgerInclusionProof {
+    #[serde(rename = "entryId")]
+    pub entry_id: String,
+    /// Commits from head back to the commit listing the entry, each a parent
+    /// of the one before. Commits with an `entriesRoot` other than the last
+    /// have their `entries` left empty.
+    pub commits: Vec<CreatedCommit>,
+}
+
+/// Entries committed after a replay cursor, and the cursor to resume from.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ReplaySince {
+    pub entries: Vec<Entry>,
+    /// The head the entries were replayed up to.
+    pub cursor: String,
+}
+
+/// The commits and entries a ledger has beyond `base`, for sending to a peer
+/// that has `base`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Bundle {
+    pub format: String,
+    pub version: String,
+    /// The commit the receiver must already have; `None` for a bundle of the
+    /// whole history, genesis included.
+    pub base: Option<String>,
+    pub head: String,
+    /// Commits in replay order.
+    pub commits: Vec<CreatedCommit>,
+    /// Every entry the commits reference.
+    pub entries: BTreeMap<String, Entry>,
+}
+
+/// How the head of ledger `a` relates to the head of ledger `b`.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HeadRelation {
+    /// Both heads are the same commit.
+    Equal,
+    /// `a` contains `b`'s head: `b` can fast-forward to `a`.
+    Ahead,
+    /// `b` contains `a`'s head: `a` can fast-forward to `b`.
+    Behind,
+    /// The heads share history but each has commits the other lacks.
+    Diverged,
+    /// The heads share no commit.
+    Unrelated,
+}
+
+/// Commits and entries one ledger has that the other lacks.
+#[derive(Serialize, Clone, Debug, PartialEq, Default)]
+pub struct LedgerSideDiff {
+    /// CommitIDs in replay order, then commits head does not reach.
+    pub commits: Vec<String>,
+    /// EntryIDs in ID order.
+    pub entries: Vec<String>,
+}
+
+/// The result of [`diff_ledgers`](crate::diff_ledgers).
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct LedgerDiff {
+    #[serde(rename = "onlyInA")]
+    pub only_in_a: LedgerSideDiff,
+    #[serde(rename = "onlyInB")]
+    pub only_in_b: LedgerSideDiff,
+    #[serde(rename = "lastCommonCommit")]
+    pub last_common_commit: Option<String>,
+    pub relation: HeadRelation,
+}
+
+/// The result of [`get_reachability_report`](crate::get_reachability_report).
+#[derive(Serialize, Clone, Debug, PartialEq, Default)]
+pub struct ReachabilityReport {
+    /// CommitIDs head does not reach, in ID order.
+    #[serde(rename = "orphanCommits")]
+    pub orphan_commits: Vec<String>,
+    /// EntryIDs no commit lists, in ID order.
+    #[serde(rename = "unreferencedEntries")]
+    pub unreferenced_entries: Vec<String>,
+    /// EntryIDs listed by more than one commit, each with those CommitIDs.
+    #[serde(rename = "sharedEntries")]
+    pub shared_entries: BTreeMap<String, Vec<String>>,
+}
+
+/// An entry in the staging area, with its derived EntryID.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct StagedEntry {
+    #[serde(rename = "entryId")]
+    pub entry_id: String,
+    pub entry: Entry,
+}
+
+/// The commit created from the staging area and the entries it committed,
+/// in staging order.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct StagedCommit {
+    #[serde(rename = "commitId")]
+    pub commit_id: String,
+    pub commit: Commit,
+    pub entries: Vec<StagedEntry>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct ValidationOptions {
+    #[serde(rename = "strictSpec")]
+    pub strict_spec: Option<bool>,
+    /// Verify every entry's Ed25519 signature against its author key.
+    #[serde(rename = "verifyEntrySignatures")]
+    pub verify_entry_signatures: Option<bool>,
+    /// Reject entry and commit timestamps that are not RFC 3339 (default
+    /// true). Appends accept them, as the TypeScript reference does.
+    #[serde(rename = "checkTimestampFormat")]
+    pub check_timestamp_format: Option<bool>,
+    /// Reject commits timestamped before one of their parents, comparing
+    /// offsets as instants (default true).
+    #[serde(rename = "checkCommitTimestampOrder")]
+    pub check_commit_timestamp_order: Option<bool>,
+    /// Reject entries timestamped after a commit that lists them (default
+    /// true).
+    #[serde(rename = "checkEntryTimestampOrder")]
+    pub check_entry_timestamp_order: Option<bool>,
+    /// The CommitID of the checkpoint a pruned ledger is trusted to start
+    /// from. Without it, a checkpoint whose parent is absent is reported as
+    /// `MISSING_COMMIT`.
+    #[serde(rename = "prunedBase")]
+    pub pruned_base: Option<String>,
+}
+
+/// Outcome of a shape or ledger validation.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ValidationResult {
+    pub ok: bool,
+    pub errors: Vec<ValidationError>,
+}
+
+impl ValidationResult {
+    pub(crate) fn from_errors(errors: Vec<ValidationError>) -> Self {
+        ValidationResult {
+            ok: errors.is_empty(),
+            errors,
+        }
+    }
+}
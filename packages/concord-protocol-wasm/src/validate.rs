@@ -0,0 +1,470 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::canonical::canonical_stringify;
+use crate::checkpoint::{checkpoint_errors, expected_heights, get_checkpoint, is_pruned_base};
+use crate::diagnostics::ValidationError;
+use crate::ids::{derive_commit_id, derive_entry_id, entry_core_value};
+use crate::ledger::{get_commit_chain, get_commit_parents};
+use crate::merkle::compute_entries_root;
+use crate::signature::verify_entry_signature;
+use crate::time::{is_before, parse_timestamp_millis};
+use crate::types::{
+    Commit, Entry, LedgerContainer, ValidationOptions, ValidationResult, LEDGER_FORMAT,
+    LEDGER_VERSION, PROTOCOL_SPEC,
+};
+
+/// `TIMESTAMP_INVALID` for a non-empty timestamp that is not RFC 3339,
+/// unless `checkTimestampFormat` is off. Only validation applies this;
+/// appends accept any non-empty timestamp, as the TypeScript reference does.
+fn timestamp_format_error(
+    timestamp: &str,
+    record: &str,
+    options: &ValidationOptions,
+) -> Option<ValidationError> {
+    let malformed = options.check_timestamp_format.unwrap_or(true)
+        && !timestamp.is_empty()
+        && parse_timestamp_millis(timestamp).is_none();
+    malformed.then(|| {
+        ValidationError::new(
+            "TIMESTAMP_INVALID",
+            format!("{}.timestamp must be an RFC 3339 timestamp", record),
+        )
+        .at("timestamp")
+    })
+}
+
+pub(crate) fn entry_errors(entry: &Entry) -> Vec<ValidationError> {
+    let mut errors: Vec<ValidationError> = Vec::new();
+    if entry.kind.is_empty() {
+        errors.push(
+            ValidationError::new("INVALID_ENTRY", "Entry.kind must be a non-empty string")
+                .at("kind"),
+        );
+    }
+    if entry.timestamp.is_empty() {
+        errors.push(
+            ValidationError::new(
+                "INVALID_ENTRY",
+                "Entry.timestamp must be a non-empty string",
+            )
+            .at("timestamp"),
+        );
+    }
+    if entry.author.is_empty() {
+        errors.push(
+            ValidationError::new("INVALID_ENTRY", "Entry.author must be a non-empty string")
+                .at("author"),
+        );
+    }
+    if let Err(error) = canonical_stringify(&entry_core_value(entry)) {
+        errors.push(ValidationError::new("INVALID_ENTRY_PAYLOAD", error.message()).at("payload"));
+    }
+    errors
+}
+
+/// One diagnostic per repeat of an EntryID in a commit's `entries`.
+pub(crate) fn duplicate_entry_errors(entries: &[String]) -> Vec<ValidationError> {
+    entries
+        .iter()
+        .enumerate()
+        .filter(|(index, entry_id)| entries[..*index].contains(entry_id))
+        .map(|(index, entry_id)| {
+            ValidationError::new(
+                "DUPLICATE_ENTRY_IN_COMMIT",
+                "Commit.entries must not repeat an EntryID",
+            )
+            .with_entry(entry_id)
+            .at(format!("entries[{}]", index))
+        })
+        .collect()
+}
+
+pub(crate) fn commit_errors(commit: &Commit) -> Vec<ValidationError> {
+    let mut errors: Vec<ValidationError> = Vec::new();
+    if commit.timestamp.is_empty() {
+        errors.push(
+            ValidationError::new(
+                "INVALID_COMMIT",
+                "Commit.timestamp must be a non-empty string",
+            )
+            .at("timestamp"),
+        );
+    }
+    if let Some(parent) = &commit.parent {
+        if parent.is_empty() {
+            errors.push(
+                ValidationError::new(
+                    "INVALID_COMMIT",
+                    "Commit.parent must be a non-empty string or null",
+                )
+                .at("parent"),
+            );
+        }
+    }
+    for (index, merge_parent) in commit.merge_parents.iter().enumerate() {
+        let message = if commit.parent.is_none() {
+            "Commit.mergeParents requires a parent"
+        } else if merge_parent.is_empty() {
+            "Commit.mergeParents must contain non-empty strings"
+        } else if commit.parent.as_ref() == Some(merge_parent)
+            || commit.merge_parents[..index].contains(merge_parent)
+        {
+            "Commit.mergeParents must not repeat a parent"
+        } else {
+            continue;
+        };
+        errors.push(
+            ValidationError::new("INVALID_COMMIT", message).at(format!("mergeParents[{}]", index)),
+        );
+    }
+    errors.extend(duplicate_entry_errors(&commit.entries));
+    if let Some(root) = &commit.entries_root {
+        if *root != compute_entries_root(&commit.entries) {
+            errors.push(
+                ValidationError::new(
+                    "ENTRIES_ROOT_MISMATCH",
+                    "Commit.entriesRoot does not match Commit.entries",
+                )
+                .at("entriesRoot"),
+            );
+        }
+    }
+    errors.extend(checkpoint_errors(commit));
+    if let Some(metadata) = &commit.metadata {
+        if !metadata.is_null() && !metadata.is_object() {
+            errors.push(
+                ValidationError::new(
+                    "INVALID_COMMIT",
+                    "Commit.metadata must be an object or null",
+                )
+                .at("metadata"),
+            );
+        }
+    }
+    errors
+}
+
+/// Joins diagnostic messages for the error thrown by an append.
+pub(crate) fn join_messages(errors: &[ValidationError]) -> String {
+    errors
+        .iter()
+        .map(|error| error.message.as_str())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+pub(crate) fn genesis_errors(genesis: &Commit, strict_spec: bool) -> Vec<ValidationError> {
+    let invalid =
+        |message: String, path: &str| ValidationError::new("GENESIS_INVALID", message).at(path);
+    let mut errors: Vec<ValidationError> = Vec::new();
+    if genesis.parent.is_some() {
+        errors.push(invalid(
+            "Genesis commit parent must be null".to_string(),
+            "parent",
+        ));
+    }
+    if !genesis.entries.is_empty() {
+        errors.push(invalid(
+            "Genesis commit entries must be an empty array".to_string(),
+            "entries",
+        ));
+    }
+    match &genesis.metadata {
+        Some(Value::Object(meta)) => {
+            if meta.get("genesis") != Some(&Value::Bool(true)) {
+                errors.push(invalid(
+                    "Genesis commit metadata.genesis must be true".to_string(),
+                    "metadata.genesis",
+                ));
+            }
+            match meta.get("spec") {
+                Some(Value::String(spec)) => {
+                    if strict_spec && spec != PROTOCOL_SPEC {
+                        errors.push(invalid(
+                            format!("Genesis commit metadata.spec must be \"{}\"", PROTOCOL_SPEC),
+                            "metadata.spec",
+                        ));
+                    }
+                }
+                Some(_) => errors.push(invalid(
+                    "Genesis commit metadata.spec must be a string".to_string(),
+                    "metadata.spec",
+                )),
+                None => errors.push(invalid(
+                    "Genesis commit metadata.spec is required".to_string(),
+                    "metadata.spec",
+                )),
+            }
+        }
+        _ => errors.push(invalid(
+            "Genesis commit metadata must be an object".to_string(),
+            "metadata",
+        )),
+    }
+    errors
+}
+
+/// Timestamp ordering diagnostics for `validate_ledger`, each rule gated by
+/// its option. Only timestamps that parse are compared; the format itself
+/// is checked with each entry and commit.
+fn timestamp_order_errors(
+    ledger: &LedgerContainer,
+    chain: &[String],
+    options: &ValidationOptions,
+) -> Vec<ValidationError> {
+    let mut errors: Vec<ValidationError> = Vec::new();
+
+    let commit_order = options.check_commit_timestamp_order.unwrap_or(true);
+    let entry_order = options.check_entry_timestamp_order.unwrap_or(true);
+    for commit_id in chain {
+        let commit = &ledger.commits[commit_id];
+        if commit_order {
+            let earlier_than_parent = get_commit_parents(commit).iter().any(|parent| {
+                ledger
+                    .commits
+                    .get(parent)
+                    .is_some_and(|parent| is_before(&commit.timestamp, &parent.timestamp))
+            });
+            if earlier_than_parent {
+                errors.push(
+                    ValidationError::new(
+                        "COMMIT_TIMESTAMP_DECREASING",
+                        "Commit timestamp must not be before its parents' timestamps",
+                    )
+                    .with_commit(commit_id)
+                    .at("timestamp"),
+                );
+            }
+        }
+        if entry_order {
+            for entry_id in &commit.entries {
+                let after_commit = ledger
+                    .entries
+                    .get(entry_id)
+                    .is_some_and(|entry| is_before(&commit.timestamp, &entry.timestamp));
+                if after_commit {
+                    errors.push(
+                        ValidationError::new(
+                            "ENTRY_TIMESTAMP_AFTER_COMMIT",
+                            "Entry timestamp must be on or before its commit timestamp",
+                        )
+                        .with_commit(commit_id)
+                        .with_entry(entry_id),
+                    );
+                }
+            }
+        }
+    }
+    errors
+}
+
+/// Validate entry shape and canonicalizability.
+pub fn validate_entry(entry: &Entry, options: &ValidationOptions) -> ValidationResult {
+    let mut errors = entry_errors(entry);
+    errors.extend(timestamp_format_error(&entry.timestamp, "Entry", options));
+    ValidationResult::from_errors(errors)
+}
+
+/// Validate commit shape without dereferencing external state.
+pub fn validate_commit(commit: &Commit, options: &ValidationOptions) -> ValidationResult {
+    let mut errors = commit_errors(commit);
+    errors.extend(timestamp_format_error(&commit.timestamp, "Commit", options));
+    ValidationResult::from_errors(errors)
+}
+
+/// Validate ledger structure, commit chain, and genesis invariants.
+pub fn validate_ledger(ledger: &LedgerContainer, options: &ValidationOptions) -> ValidationResult {
+    let strict_spec = options.strict_spec.unwrap_or(true);
+    let verify_signatures = options.verify_entry_signatures.unwrap_or(false);
+
+    let mut errors: Vec<ValidationError> = Vec::new();
+    if ledger.format != LEDGER_FORMAT {
+        errors.push(
+            ValidationError::new(
+                "LEDGER_FORMAT_INVALID",
+                format!("Ledger.format must be \"{}\"", LEDGER_FORMAT),
+            )
+            .at("format"),
+        );
+    }
+    if ledger.version != LEDGER_VERSION {
+        errors.push(
+            ValidationError::new(
+                "LEDGER_VERSION_INVALID",
+                format!("Ledger.version must be \"{}\"", LEDGER_VERSION),
+            )
+            .at("version"),
+        );
+    }
+    if ledger.head.is_empty() {
+        errors.push(
+            ValidationError::new("LEDGER_HEAD_INVALID", "Ledger.head must be a string").at("head"),
+        );
+    }
+    if !errors.is_empty() {
+        return ValidationResult::from_errors(errors);
+    }
+
+    if !ledger.commits.contains_key(&ledger.head) {
+        errors.push(
+            ValidationError::new(
+                "MISSING_HEAD",
+                format!("Ledger head {} does not exist in commits", ledger.head),
+            )
+            .at("head"),
+        );
+    }
+    let chain = get_commit_chain(ledger).unwrap_or_else(|error| {
+        errors.push(ValidationError::new(error.code(), error.message()));
+        Vec::new()
+    });
+
+    // History may only stop short of genesis at the pruned base the caller
+    // expects; anywhere else the checkpoint's parent is simply missing.
+    if let Some((genesis_id, genesis)) = chain
+        .first()
+        .and_then(|genesis_id| ledger.commits.get_key_value(genesis_id))
+    {
+        if !is_pruned_base(ledger, genesis) {
+            errors.extend(
+                genesis_errors(genesis, strict_spec)
+                    .into_iter()
+                    .map(|error| error.with_commit(genesis_id)),
+            );
+        } else if options.pruned_base.as_ref() != Some(genesis_id) {
+            errors.push(
+                ValidationError::new(
+                    "MISSING_COMMIT",
+                    format!(
+                        "Missing commit {}; pass prunedBase to accept a pruned ledger",
+                        genesis.parent.as_deref().unwrap_or_default()
+                    ),
+                )
+                .with_commit(genesis_id)
+                .at("parent"),
+            );
+        }
+    }
+    // Merged branches must all grow from the one genesis commit.
+    for commit_id in chain.iter().skip(1) {
+        if ledger.commits[commit_id].parent.is_none() {
+            errors.push(
+                ValidationError::new(
+                    "GENESIS_INVALID",
+                    "Only the genesis commit may have a null parent",
+                )
+                .with_commit(commit_id)
+                .at("parent"),
+            );
+        }
+    }
+
+    // A pruned base vouches for its own height; later checkpoints count up
+    // from it.
+    for (commit_id, height) in expected_heights(ledger, &chain).unwrap_or_default() {
+        let recorded =
+            get_checkpoint(&ledger.commits[commit_id]).map(|checkpoint| checkpoint.height);
+        if recorded != Some(height) {
+            errors.push(
+                ValidationError::new(
+                    "INVALID_CHECKPOINT",
+                    format!("Checkpoint height must be {}", height),
+                )
+                .with_commit(commit_id)
+                .at(r#"metadata["concord/checkpoint"].height"#),
+            );
+        }
+    }
+
+    errors.extend(timestamp_order_errors(ledger, &chain, options));
+
+    // Replay would apply an entry once per chain commit listing it.
+    let mut committed_in: BTreeMap<&String, &String> = BTreeMap::new();
+    for commit_id in &chain {
+        for (index, entry_id) in ledger.commits[commit_id].entries.iter().enumerate() {
+            match committed_in.get(entry_id) {
+                Some(first) if *first != commit_id => errors.push(
+                    ValidationError::new(
+                        "ENTRY_ALREADY_COMMITTED",
+                        format!("Entry {} is already committed in {}", entry_id, first),
+                    )
+                    .with_commit(commit_id)
+                    .with_entry(entry_id)
+                    .at(format!("entries[{}]", index)),
+                ),
+                Some(_) => {}
+                None => {
+                    committed_in.insert(entry_id, commit_id);
+                }
+            }
+        }
+    }
+
+    for (commit_id, commit) in &ledger.commits {
+        errors.extend(
+            commit_errors(commit)
+                .into_iter()
+                .chain(timestamp_format_error(&commit.timestamp, "Commit", options))
+                .map(|error| error.with_commit(commit_id)),
+        );
+        if let Ok(derived) = derive_commit_id(commit) {
+            if &derived != commit_id {
+                errors.push(
+                    ValidationError::new(
+                        "COMMIT_ID_MISMATCH",
+                        format!(
+                            "CommitID does not match commit content (derived {})",
+                            derived
+                        ),
+                    )
+                    .with_commit(commit_id),
+                );
+            }
+        }
+        for (index, entry_id) in commit.entries.iter().enumerate() {
+            if !ledger.entries.contains_key(entry_id) {
+                errors.push(
+                    ValidationError::new(
+                        "MISSING_ENTRY",
+                        format!("Commit {} references missing entry {}", commit_id, entry_id),
+                    )
+                    .with_commit(commit_id)
+                    .with_entry(entry_id)
+                    .at(format!("entries[{}]", index)),
+                );
+            }
+        }
+    }
+
+    for (entry_id, entry) in &ledger.entries {
+        errors.extend(
+            entry_errors(entry)
+                .into_iter()
+                .chain(timestamp_format_error(&entry.timestamp, "Entry", options))
+                .map(|error| error.with_entry(entry_id)),
+        );
+        if let Ok(derived) = derive_entry_id(entry) {
+            if &derived != entry_id {
+                errors.push(
+                    ValidationError::new(
+                        "ENTRY_ID_MISMATCH",
+                        format!("EntryID does not match entry content (derived {})", derived),
+                    )
+                    .with_entry(entry_id),
+                );
+            }
+        }
+        if verify_signatures {
+            if let Err(error) = verify_entry_signature(entry) {
+                errors.push(
+                    ValidationError::new(error.code(), error.message())
+                        .with_entry(entry_id)
+                        .at("signature"),
+                );
+            }
+        }
+    }
+
+    ValidationResult::from_errors(errors)
+}
@@ -0,0 +1,1014 @@
+//! `#[wasm_bindgen]` exports. Each function converts between `JsValue` and the
+//! native types and delegates to the protocol core.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use wasm_bindgen::prelude::*;
+
+use crate::assertions::Assertion;
+use crate::diagnostics::ValidationError;
+use crate::epochs::{EpochIdParams, EpochValidationOptions};
+use crate::error::ConcordError;
+use crate::input::InputValue;
+use crate::staging::StagedLedger;
+use crate::types::{
+    Bundle, Commit, CommitParams, Entry, EntryInclusionProof, LedgerContainer,
+    LedgerInclusionProof, ValidationOptions, ValidationResult,
+};
+
+#[derive(Deserialize)]
+struct CreateCommitParams {
+    pub ledger: LedgerContainer,
+    #[serde(flatten)]
+    pub params: CommitParams,
+}
+
+type JsResult<T> = Result<T, ConcordError>;
+
+#[wasm_bindgen(inline_js = r#"
+export class ConcordError extends Error {
+  constructor(code, message, context) {
+    super(message);
+    this.name = "ConcordError";
+    this.code = code;
+    Object.assign(this, context);
+  }
+}
+
+export function concordErrorClass() {
+  return ConcordError;
+}
+"#)]
+extern "C" {
+    #[wasm_bindgen(js_name = ConcordError, extends = js_sys::Error)]
+    type JsConcordError;
+
+    #[wasm_bindgen(constructor, js_class = ConcordError)]
+    fn new(code: &str, message: &str, context: &JsValue) -> JsConcordError;
+
+    #[wasm_bindgen(js_name = concordErrorClass)]
+    fn concord_error_class_js() -> JsValue;
+}
+
+impl From<ConcordError> for JsValue {
+    fn from(error: ConcordError) -> Self {
+        let context = to_js(&error.context()).unwrap_or_else(|_| js_sys::Object::new().into());
+        JsConcordError::new(error.code(), &error.message(), &context).into()
+    }
+}
+
+impl From<serde_wasm_bindgen::Error> for ConcordError {
+    fn from(error: serde_wasm_bindgen::Error) -> Self {
+        ConcordError::Deserialization {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// The `ConcordError` class every export throws, for `instanceof` checks.
+#[wasm_bindgen]
+pub fn concord_error_class() -> JsValue {
+    concord_error_class_js()
+}
+
+/// Converts to plain JS objects and arrays (never `Map`), so results can be
+/// passed straight back into other exports.
+fn to_js<T: Serialize + ?Sized>(value: &T) -> JsResult<JsValue> {
+    Ok(value.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?)
+}
+
+/// Walks a JS value the way `canonicalize` in `canonical.ts` does, keeping the
+/// values JSON cannot represent so they are rejected with the same messages.
+fn input_from_js(value: &JsValue, ancestors: &mut Vec<JsValue>) -> InputValue {
+    if value.is_undefined() {
+        return InputValue::Undefined;
+    }
+    if value.is_null() {
+        return InputValue::Null;
+    }
+    if let Some(value) = value.as_bool() {
+        return InputValue::Bool(value);
+    }
+    if let Some(value) = value.as_f64() {
+        return InputValue::Number(value);
+    }
+    if let Some(value) = value.as_string() {
+        return InputValue::String(value);
+    }
+    if value.is_function() {
+        return InputValue::Unsupported("function".to_string());
+    }
+    if value.is_symbol() {
+        return InputValue::Unsupported("symbol".to_string());
+    }
+    if value.is_bigint() {
+        return InputValue::Unsupported("bigint".to_string());
+    }
+    if ancestors
+        .iter()
+        .any(|ancestor| js_sys::Object::is(ancestor, value))
+    {
+        return InputValue::Circular;
+    }
+    if js_sys::Reflect::get(value, &JsValue::from_str("toJSON"))
+        .map(|to_json| to_json.is_function())
+        .unwrap_or(false)
+    {
+        return InputValue::Unsupported("toJSON".to_string());
+    }
+
+    ancestors.push(value.clone());
+    let input = if js_sys::Array::is_array(value) {
+        let items = js_sys::Array::from(value)
+            .iter()
+            .map(|item| input_from_js(&item, ancestors))
+            .collect();
+        InputValue::Array(items)
+    } else {
+        let object: &js_sys::Object = value.unchecked_ref();
+        let fields = js_sys::Object::keys(object)
+            .iter()
+            .filter_map(|key| {
+                let child = js_sys::Reflect::get(value, &key).ok()?;
+                Some((key.as_string()?, input_from_js(&child, ancestors)))
+            })
+            .collect();
+        InputValue::Object(fields)
+    };
+    ancestors.pop();
+    input
+}
+
+fn json_from_js(value: &JsValue) -> JsResult<Value> {
+    input_from_js(value, &mut Vec::new()).into_json()
+}
+
+fn entry_from_js(value: &JsValue) -> JsResult<Entry> {
+    Entry::from_input(input_from_js(value, &mut Vec::new()))
+}
+
+fn parse_optional_metadata(value: JsValue) -> JsResult<Option<Value>> {
+    if value.is_null() || value.is_undefined() {
+        return Ok(None);
+    }
+    Ok(Some(serde_wasm_bindgen::from_value(value)?))
+}
+
+/// Canonical JSON serialization with lexicographically sorted keys.
+#[wasm_bindgen]
+pub fn canonical_stringify(value: JsValue) -> JsResult<JsValue> {
+    let value = json_from_js(&value)?;
+    Ok(JsValue::from_str(&crate::canonical_stringify(&value)?))
+}
+
+/// SHA-256 hash of the canonical JSON serialization.
+#[wasm_bindgen]
+pub fn hash_data(value: JsValue) -> JsResult<JsValue> {
+    let value = json_from_js(&value)?;
+    Ok(JsValue::from_str(&crate::hash_data(&value)?))
+}
+
+/// Canonical signing payload bytes for an entry (excludes signature).
+#[wasm_bindgen]
+pub fn get_entry_signing_bytes(entry: JsValue) -> JsResult<JsValue> {
+    let entry = entry_from_js(&entry)?;
+    let bytes = crate::get_entry_signing_bytes(&entry)?;
+    Ok(js_sys::Uint8Array::from(bytes.as_slice()).into())
+}
+
+/// Canonical signing payload for an entry (excludes signature).
+#[wasm_bindgen]
+pub fn get_entry_signing_payload(entry: JsValue) -> JsResult<JsValue> {
+    let entry = entry_from_js(&entry)?;
+    Ok(JsValue::from_str(&crate::get_entry_signing_payload(
+        &entry,
+    )?))
+}
+
+/// Deterministically derives an EntryID from entry content.
+#[wasm_bindgen]
+pub fn derive_entry_id(entry: JsValue) -> JsResult<JsValue> {
+    let entry = entry_from_js(&entry)?;
+    Ok(JsValue::from_str(&crate::derive_entry_id(&entry)?))
+}
+
+/// Canonical signing payload bytes for a commit (excludes signature).
+#[wasm_bindgen]
+pub fn get_commit_signing_bytes(commit: JsValue) -> JsResult<JsValue> {
+    let commit: Commit = serde_wasm_bindgen::from_value(commit)?;
+    let bytes = crate::get_commit_signing_bytes(&commit)?;
+    Ok(js_sys::Uint8Array::from(bytes.as_slice()).into())
+}
+
+/// Canonical signing payload for a commit (excludes signature).
+#[wasm_bindgen]
+pub fn get_commit_signing_payload(commit: JsValue) -> JsResult<JsValue> {
+    let commit: Commit = serde_wasm_bindgen::from_value(commit)?;
+    Ok(JsValue::from_str(&crate::get_commit_signing_payload(
+        &commit,
+    )?))
+}
+
+/// Deterministically derives a CommitID from commit content.
+#[wasm_bindgen]
+pub fn derive_commit_id(commit: JsValue) -> JsResult<JsValue> {
+    let commit: Commit = serde_wasm_bindgen::from_value(commit)?;
+    Ok(JsValue::from_str(&crate::derive_commit_id(&commit)?))
+}
+
+/// Creates the genesis commit for a new ledger.
+#[wasm_bindgen]
+pub fn create_genesis_commit(metadata: JsValue, timestamp: Option<String>) -> JsResult<JsValue> {
+    let metadata = parse_optional_metadata(metadata)?;
+    let created = crate::create_genesis_commit(metadata, timestamp)?;
+    to_js(&created)
+}
+
+/// Creates a new ledger container with a genesis commit.
+#[wasm_bindgen]
+pub fn create_ledger(metadata: JsValue, timestamp: Option<String>) -> JsResult<JsValue> {
+    let metadata = parse_optional_metadata(metadata)?;
+    let ledger = crate::create_ledger(metadata, timestamp)?;
+    to_js(&ledger)
+}
+
+/// Deterministic CBOR encoding of a ledger, with IDs as 32-byte hashes.
+#[wasm_bindgen]
+pub fn encode_ledger_cbor(ledger: JsValue) -> JsResult<js_sys::Uint8Array> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    Ok(js_sys::Uint8Array::from(
+        crate::encode_ledger_cbor(&ledger).as_slice(),
+    ))
+}
+
+/// Decodes a ledger produced by `encode_ledger_cbor` back to its JSON form.
+#[wasm_bindgen]
+pub fn decode_ledger_cbor(bytes: &[u8]) -> JsResult<JsValue> {
+    to_js(&crate::decode_ledger_cbor(bytes)?)
+}
+
+/// Serializes a ledger as NDJSON: a header line, then entries and commits in
+/// replay order.
+#[wasm_bindgen]
+pub fn export_ledger_ndjson(ledger: JsValue) -> JsResult<String> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    crate::export_ledger_ndjson(&ledger)
+}
+
+/// Returns commit IDs from genesis to head in replay order.
+#[wasm_bindgen]
+pub fn get_commit_chain(ledger: JsValue) -> JsResult<JsValue> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    to_js(&crate::get_commit_chain(&ledger)?)
+}
+
+/// True when the commit is the Concord genesis commit.
+#[wasm_bindgen]
+pub fn is_genesis_commit(commit: JsValue) -> JsResult<bool> {
+    let commit: Commit = serde_wasm_bindgen::from_value(commit)?;
+    Ok(crate::is_genesis_commit(&commit))
+}
+
+/// The `{ height, stateHash }` a checkpoint commit records, or `null`.
+#[wasm_bindgen]
+pub fn get_checkpoint(commit: JsValue) -> JsResult<JsValue> {
+    let commit: Commit = serde_wasm_bindgen::from_value(commit)?;
+    to_js(&crate::get_checkpoint(&commit))
+}
+
+/// Creates a checkpoint commit on head recording the projection state hash.
+#[wasm_bindgen]
+pub fn create_checkpoint_commit(
+    ledger: JsValue,
+    state_hash: String,
+    metadata: JsValue,
+    timestamp: Option<String>,
+) -> JsResult<JsValue> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    let metadata = parse_optional_metadata(metadata)?;
+    to_js(&crate::create_checkpoint_commit(
+        &ledger, state_hash, metadata, timestamp,
+    )?)
+}
+
+/// Drops the history behind a checkpoint, which becomes the ledger's base.
+#[wasm_bindgen]
+pub fn prune_ledger(ledger: JsValue, checkpoint_id: String) -> JsResult<JsValue> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    to_js(&crate::prune_ledger(&ledger, &checkpoint_id)?)
+}
+
+/// Commits head does not reach, entries no commit lists, and entries listed
+/// by more than one commit, as
+/// `{ orphanCommits, unreferencedEntries, sharedEntries }`.
+#[wasm_bindgen]
+pub fn get_reachability_report(ledger: JsValue) -> JsResult<JsValue> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    to_js(&crate::get_reachability_report(&ledger)?)
+}
+
+/// Drops commits head does not reach and entries no kept commit lists,
+/// keeping any CommitIDs or EntryIDs in `roots`.
+#[wasm_bindgen]
+pub fn gc_ledger(ledger: JsValue, roots: Option<Vec<String>>) -> JsResult<JsValue> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    to_js(&crate::gc_ledger(&ledger, &roots.unwrap_or_default())?)
+}
+
+/// Creates a non-genesis commit with validated parent and entry references.
+#[wasm_bindgen]
+pub fn create_commit(params: JsValue) -> JsResult<JsValue> {
+    let CreateCommitParams { ledger, params } = serde_wasm_bindgen::from_value(params)?;
+    let created = crate::create_commit(&ledger, params)?;
+    to_js(&created)
+}
+
+/// Appends a commit to a ledger with validation.
+#[wasm_bindgen]
+pub fn append_commit(ledger: JsValue, commit_id: String, commit: JsValue) -> JsResult<JsValue> {
+    let mut ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    let commit: Commit = serde_wasm_bindgen::from_value(commit)?;
+    crate::append_commit(&mut ledger, commit_id, commit)?;
+    to_js(&ledger)
+}
+
+/// Append a commit and verify its CommitID matches the commit content.
+#[wasm_bindgen]
+pub fn append_commit_strict(
+    ledger: JsValue,
+    commit_id: String,
+    commit: JsValue,
+) -> JsResult<JsValue> {
+    let mut ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    let commit: Commit = serde_wasm_bindgen::from_value(commit)?;
+    crate::append_commit_strict(&mut ledger, commit_id, commit)?;
+    to_js(&ledger)
+}
+
+/// Append an entry after validating shape and canonicalizability.
+#[wasm_bindgen]
+pub fn append_entry(ledger: JsValue, entry: JsValue) -> JsResult<JsValue> {
+    let mut ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    let entry = entry_from_js(&entry)?;
+    let entry_id = crate::append_entry(&mut ledger, entry)?;
+    to_js(&serde_json::json!({
+        "entryId": entry_id,
+        "ledger": ledger
+    }))
+}
+
+/// Returns entry IDs in deterministic replay order (skipping genesis).
+#[wasm_bindgen]
+pub fn get_replay_entry_ids(ledger: JsValue) -> JsResult<JsValue> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    to_js(&crate::get_replay_entry_ids(&ledger)?)
+}
+
+/// Resolve entries in deterministic replay order.
+#[wasm_bindgen]
+pub fn get_replay_entries(ledger: JsValue) -> JsResult<JsValue> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    to_js(&crate::get_replay_entries(&ledger)?)
+}
+
+/// Entries committed after the `commit_id` cursor, as `{ entries, cursor }`.
+/// Throws `CURSOR_NOT_ON_CHAIN` when the cursor is not an ancestor of head.
+#[wasm_bindgen]
+pub fn get_replay_entries_since(ledger: JsValue, commit_id: String) -> JsResult<JsValue> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    to_js(&crate::get_replay_entries_since(&ledger, &commit_id)?)
+}
+
+/// The best common ancestor of two commits, or `null` for unrelated histories.
+#[wasm_bindgen]
+pub fn get_merge_base(ledger: JsValue, a: String, b: String) -> JsResult<Option<String>> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    crate::get_merge_base(&ledger, &a, &b)
+}
+
+/// True when `ancestor` is `commit_id` or one of its ancestors.
+#[wasm_bindgen]
+pub fn is_ancestor(ledger: JsValue, ancestor: String, commit_id: String) -> JsResult<bool> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    crate::is_ancestor(&ledger, &ancestor, &commit_id)
+}
+
+/// Reconciles two copies of a ledger, fast-forwarding or adding a merge
+/// commit. Returns the merged ledger.
+#[wasm_bindgen]
+pub fn merge_ledgers(
+    ours: JsValue,
+    theirs: JsValue,
+    metadata: JsValue,
+    timestamp: Option<String>,
+) -> JsResult<JsValue> {
+    let ours: LedgerContainer = serde_wasm_bindgen::from_value(ours)?;
+    let theirs: LedgerContainer = serde_wasm_bindgen::from_value(theirs)?;
+    let metadata = parse_optional_metadata(metadata)?;
+    to_js(&crate::merge_ledgers(&ours, &theirs, metadata, timestamp)?)
+}
+
+/// What each ledger has that the other lacks, as
+/// `{ onlyInA, onlyInB, lastCommonCommit, relation }`.
+#[wasm_bindgen]
+pub fn diff_ledgers(a: JsValue, b: JsValue) -> JsResult<JsValue> {
+    let a: LedgerContainer = serde_wasm_bindgen::from_value(a)?;
+    let b: LedgerContainer = serde_wasm_bindgen::from_value(b)?;
+    to_js(&crate::diff_ledgers(&a, &b)?)
+}
+
+/// Bundles the commits and entries reachable from head but not from `base`
+/// (the whole history when `base` is omitted).
+#[wasm_bindgen]
+pub fn create_bundle(ledger: JsValue, base: Option<String>) -> JsResult<JsValue> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    to_js(&crate::create_bundle(&ledger, base.as_deref())?)
+}
+
+/// Verifies and imports a bundle, then fast-forwards head. Returns
+/// `{ commits, ledger }` where `commits` are the CommitIDs added.
+#[wasm_bindgen]
+pub fn apply_bundle(ledger: JsValue, bundle: JsValue) -> JsResult<JsValue> {
+    let mut ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    let bundle: Bundle = serde_wasm_bindgen::from_value(bundle)?;
+    let commits = crate::apply_bundle(&mut ledger, &bundle)?;
+    to_js(&serde_json::json!({
+        "commits": commits,
+        "ledger": ledger
+    }))
+}
+
+/// The Merkle root of a list of EntryIDs, as recorded in `entriesRoot`.
+#[wasm_bindgen]
+pub fn compute_entries_root(entry_ids: Vec<String>) -> String {
+    crate::compute_entries_root(&entry_ids)
+}
+
+/// Proves that an entry is listed in a commit with an `entriesRoot`.
+#[wasm_bindgen]
+pub fn prove_entry_inclusion(
+    ledger: JsValue,
+    commit_id: String,
+    entry_id: String,
+) -> JsResult<JsValue> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    to_js(&crate::prove_entry_inclusion(
+        &ledger, &commit_id, &entry_id,
+    )?)
+}
+
+/// Checks an inclusion proof against its entry without the rest of the
+/// commit. Malformed input verifies as `false`.
+#[wasm_bindgen]
+pub fn verify_entry_inclusion(entry: JsValue, proof: JsValue) -> bool {
+    let entry = match entry_from_js(&entry) {
+        Ok(entry) => entry,
+        Err(_) => return false,
+    };
+    match serde_wasm_bindgen::from_value::<EntryInclusionProof>(proof) {
+        Ok(proof) => crate::verify_entry_inclusion(&entry, &proof),
+        Err(_) => false,
+    }
+}
+
+/// Proves that an entry is committed in the history of the ledger head.
+#[wasm_bindgen]
+pub fn prove_entry_in_ledger(ledger: JsValue, entry_id: String) -> JsResult<JsValue> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    to_js(&crate::prove_entry_in_ledger(&ledger, &entry_id)?)
+}
+
+/// Checks a `prove_entry_in_ledger` proof against a trusted head.
+/// Malformed input verifies as `false`.
+#[wasm_bindgen]
+pub fn verify_entry_in_ledger(entry: JsValue, proof: JsValue, head: String) -> bool {
+    let entry = match entry_from_js(&entry) {
+        Ok(entry) => entry,
+        Err(_) => return false,
+    };
+    match serde_wasm_bindgen::from_value::<LedgerInclusionProof>(proof) {
+        Ok(proof) => crate::verify_entry_in_ledger(&entry, &proof, &head),
+        Err(_) => false,
+    }
+}
+
+/// Validate entry shape and canonicalizability.
+#[wasm_bindgen]
+pub fn validate_entry(entry: JsValue, options: JsValue) -> JsResult<JsValue> {
+    let options = parse_validation_options(options)?;
+    let result = match Entry::from_input(input_from_js(&entry, &mut Vec::new())) {
+        Ok(entry) => crate::validate_entry(&entry, &options),
+        Err(ConcordError::InvalidEntry { errors, .. }) => ValidationResult::from_errors(errors),
+        Err(error) => ValidationResult::from_errors(vec![ValidationError::new(
+            "INVALID_ENTRY",
+            error.message(),
+        )]),
+    };
+    to_js(&result)
+}
+
+/// Verify an entry's Ed25519 signature against its author key. Throws
+/// `ENTRY_SIGNATURE_MISSING` or `ENTRY_SIGNATURE_INVALID` on failure.
+#[wasm_bindgen]
+pub fn verify_entry_signature(entry: JsValue) -> JsResult<()> {
+    let entry = entry_from_js(&entry)?;
+    crate::verify_entry_signature(&entry)
+}
+
+/// Validate commit shape without dereferencing external state.
+#[wasm_bindgen]
+pub fn validate_commit(commit: JsValue, options: JsValue) -> JsResult<JsValue> {
+    let commit: Commit = serde_wasm_bindgen::from_value(commit)?;
+    let options = parse_validation_options(options)?;
+    to_js(&crate::validate_commit(&commit, &options))
+}
+
+fn parse_validation_options(options: JsValue) -> JsResult<ValidationOptions> {
+    if options.is_null() || options.is_undefined() {
+        return Ok(ValidationOptions::default());
+    }
+    Ok(serde_wasm_bindgen::from_value(options)?)
+}
+
+/// Validate ledger structure, commit chain, and genesis invariants.
+#[wasm_bindgen]
+pub fn validate_ledger(ledger: JsValue, options: JsValue) -> JsResult<JsValue> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    let options = parse_validation_options(options)?;
+    to_js(&crate::validate_ledger(&ledger, &options))
+}
+
+/// A ledger held in WASM memory.
+///
+/// The free functions take and return the whole container, so every call
+/// pays for converting it across the JS boundary. `Ledger` keeps the
+/// container on the Rust side and only converts the values a method takes or
+/// returns; `export()` produces the plain container when it is needed.
+///
+/// Entries can be staged with `stage` / `stage_many` and committed together
+/// with `commit`; staged entries are never part of the exported container.
+#[wasm_bindgen]
+pub struct Ledger {
+    inner: StagedLedger,
+}
+
+#[wasm_bindgen]
+impl Ledger {
+    /// Creates a new ledger with a genesis commit.
+    #[wasm_bindgen(constructor)]
+    pub fn new(metadata: JsValue, timestamp: Option<String>) -> JsResult<Ledger> {
+        let metadata = parse_optional_metadata(metadata)?;
+        Ok(Ledger {
+            inner: StagedLedger::new(crate::create_ledger(metadata, timestamp)?),
+        })
+    }
+
+    /// Loads an existing ledger container with an empty staging area. The
+    /// container is not validated; call `validate()` for untrusted input.
+    pub fn load(ledger: JsValue) -> JsResult<Ledger> {
+        let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+        Ok(Ledger {
+            inner: StagedLedger::new(ledger),
+        })
+    }
+
+    /// Loads a ledger from `export_ledger_ndjson` text in one piece. Use
+    /// `NdjsonReader` to load a stream chunk by chunk. Pass the checkpoint
+    /// ID to load a pruned ledger that starts there.
+    pub fn load_ndjson(text: &str, pruned_base: Option<String>) -> JsResult<Ledger> {
+        let mut reader = pruned_base.map_or_else(
+            crate::NdjsonReader::new,
+            crate::NdjsonReader::with_pruned_base,
+        );
+        reader.push(text)?;
+        Ok(Ledger {
+            inner: StagedLedger::new(reader.finish()?),
+        })
+    }
+
+    /// Loads a ledger from `encode_ledger_cbor` bytes.
+    pub fn load_cbor(bytes: &[u8]) -> JsResult<Ledger> {
+        Ok(Ledger {
+            inner: StagedLedger::new(crate::decode_ledger_cbor(bytes)?),
+        })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn head(&self) -> String {
+        self.inner.ledger().head.clone()
+    }
+
+    /// Staged entries as `{ entryId, entry }`, in staging order.
+    #[wasm_bindgen(getter)]
+    pub fn staged(&self) -> JsResult<JsValue> {
+        to_js(self.inner.staged())
+    }
+
+    /// Append an entry after validating shape and canonicalizability.
+    /// Returns the EntryID.
+    pub fn append_entry(&mut self, entry: JsValue) -> JsResult<String> {
+        let entry = entry_from_js(&entry)?;
+        crate::append_entry(self.inner.ledger_mut(), entry)
+    }
+
+    /// Stages an entry for the next `commit`. Returns the EntryID.
+    pub fn stage(&mut self, entry: JsValue) -> JsResult<String> {
+        let entry = entry_from_js(&entry)?;
+        self.inner.stage(entry)
+    }
+
+    /// Stages entries in order; nothing is staged if any entry is rejected.
+    /// Returns the EntryIDs.
+    pub fn stage_many(&mut self, entries: JsValue) -> JsResult<JsValue> {
+        if !js_sys::Array::is_array(&entries) {
+            return Err(ConcordError::Deserialization {
+                message: "entries must be an array".to_string(),
+            });
+        }
+        let entries = js_sys::Array::from(&entries)
+            .iter()
+            .map(|entry| entry_from_js(&entry))
+            .collect::<JsResult<Vec<Entry>>>()?;
+        to_js(&self.inner.stage_many(entries)?)
+    }
+
+    /// Discards every staged entry.
+    pub fn clear_staged(&mut self) {
+        self.inner.clear_staged();
+    }
+
+    /// Commits the staged entries in staging order. Throws `NOTHING_STAGED`
+    /// when nothing is staged; staging is cleared only on success. Returns
+    /// `{ commitId, commit, entries }`.
+    pub fn commit(&mut self, metadata: JsValue, timestamp: Option<String>) -> JsResult<JsValue> {
+        let metadata = parse_optional_metadata(metadata)?;
+        to_js(&self.inner.commit(metadata, timestamp)?)
+    }
+
+    /// Appends a checkpoint commit on head recording `state_hash`. Staged
+    /// entries stay staged. Returns `{ commitId, commit }`.
+    pub fn checkpoint(
+        &mut self,
+        state_hash: String,
+        metadata: JsValue,
+        timestamp: Option<String>,
+    ) -> JsResult<JsValue> {
+        let metadata = parse_optional_metadata(metadata)?;
+        let created =
+            crate::create_checkpoint_commit(self.inner.ledger(), state_hash, metadata, timestamp)?;
+        crate::append_commit(
+            self.inner.ledger_mut(),
+            created.commit_id.clone(),
+            created.commit.clone(),
+        )?;
+        to_js(&created)
+    }
+
+    /// Drops the history behind a checkpoint on the chain.
+    pub fn prune(&mut self, checkpoint_id: String) -> JsResult<()> {
+        *self.inner.ledger_mut() = crate::prune_ledger(self.inner.ledger(), &checkpoint_id)?;
+        Ok(())
+    }
+
+    /// Reports orphan commits, unreferenced entries and shared entries.
+    pub fn reachability_report(&self) -> JsResult<JsValue> {
+        to_js(&crate::get_reachability_report(self.inner.ledger())?)
+    }
+
+    /// Drops commits head does not reach and entries no kept commit lists,
+    /// keeping any CommitIDs or EntryIDs in `roots`.
+    pub fn gc(&mut self, roots: Option<Vec<String>>) -> JsResult<()> {
+        *self.inner.ledger_mut() =
+            crate::gc_ledger(self.inner.ledger(), &roots.unwrap_or_default())?;
+        Ok(())
+    }
+
+    /// Creates a commit on this ledger without appending it.
+    pub fn create_commit(&self, params: JsValue) -> JsResult<JsValue> {
+        let params: CommitParams = serde_wasm_bindgen::from_value(params)?;
+        to_js(&crate::create_commit(self.inner.ledger(), params)?)
+    }
+
+    /// Appends a commit with validation.
+    pub fn append_commit(&mut self, commit_id: String, commit: JsValue) -> JsResult<()> {
+        let commit: Commit = serde_wasm_bindgen::from_value(commit)?;
+        crate::append_commit(self.inner.ledger_mut(), commit_id, commit)
+    }
+
+    /// Appends a commit and verifies its CommitID matches the commit content.
+    pub fn append_commit_strict(&mut self, commit_id: String, commit: JsValue) -> JsResult<()> {
+        let commit: Commit = serde_wasm_bindgen::from_value(commit)?;
+        crate::append_commit_strict(self.inner.ledger_mut(), commit_id, commit)
+    }
+
+    /// Reconciles another copy of this ledger into this one, fast-forwarding
+    /// or adding a merge commit. Staged entries are kept.
+    pub fn merge(
+        &mut self,
+        theirs: JsValue,
+        metadata: JsValue,
+        timestamp: Option<String>,
+    ) -> JsResult<()> {
+        let theirs: LedgerContainer = serde_wasm_bindgen::from_value(theirs)?;
+        let metadata = parse_optional_metadata(metadata)?;
+        *self.inner.ledger_mut() =
+            crate::merge_ledgers(self.inner.ledger(), &theirs, metadata, timestamp)?;
+        Ok(())
+    }
+
+    /// Compares this ledger (as `a`) with another container (as `b`).
+    pub fn diff(&self, other: JsValue) -> JsResult<JsValue> {
+        let other: LedgerContainer = serde_wasm_bindgen::from_value(other)?;
+        to_js(&crate::diff_ledgers(self.inner.ledger(), &other)?)
+    }
+
+    /// Bundles the commits and entries reachable from head but not from
+    /// `base`.
+    pub fn create_bundle(&self, base: Option<String>) -> JsResult<JsValue> {
+        to_js(&crate::create_bundle(self.inner.ledger(), base.as_deref())?)
+    }
+
+    /// Verifies and imports a bundle, then fast-forwards head. Returns the
+    /// CommitIDs added.
+    pub fn apply_bundle(&mut self, bundle: JsValue) -> JsResult<JsValue> {
+        let bundle: Bundle = serde_wasm_bindgen::from_value(bundle)?;
+        to_js(&crate::apply_bundle(self.inner.ledger_mut(), &bundle)?)
+    }
+
+    /// Proves that an entry is listed in a commit with an `entriesRoot`.
+    pub fn prove_entry_inclusion(&self, commit_id: String, entry_id: String) -> JsResult<JsValue> {
+        to_js(&crate::prove_entry_inclusion(
+            self.inner.ledger(),
+            &commit_id,
+            &entry_id,
+        )?)
+    }
+
+    /// Proves that an entry is committed in the history of head.
+    pub fn prove_entry_in_ledger(&self, entry_id: String) -> JsResult<JsValue> {
+        to_js(&crate::prove_entry_in_ledger(
+            self.inner.ledger(),
+            &entry_id,
+        )?)
+    }
+
+    /// The best common ancestor of two commits, or `null`.
+    pub fn get_merge_base(&self, a: String, b: String) -> JsResult<Option<String>> {
+        crate::get_merge_base(self.inner.ledger(), &a, &b)
+    }
+
+    /// Returns commit IDs from genesis to head in replay order.
+    pub fn get_commit_chain(&self) -> JsResult<JsValue> {
+        to_js(&crate::get_commit_chain(self.inner.ledger())?)
+    }
+
+    /// Returns entry IDs in deterministic replay order (skipping genesis).
+    pub fn get_replay_entry_ids(&self) -> JsResult<JsValue> {
+        to_js(&crate::get_replay_entry_ids(self.inner.ledger())?)
+    }
+
+    /// Resolve entries in deterministic replay order, with staged entries
+    /// last when `include_staged` is true.
+    pub fn get_replay_entries(&self, include_staged: Option<bool>) -> JsResult<JsValue> {
+        to_js(
+            &self
+                .inner
+                .get_replay_entries(include_staged.unwrap_or(false))?,
+        )
+    }
+
+    /// Committed entries after the `commit_id` cursor, as
+    /// `{ entries, cursor }`.
+    pub fn get_replay_entries_since(&self, commit_id: String) -> JsResult<JsValue> {
+        to_js(&crate::get_replay_entries_since(
+            self.inner.ledger(),
+            &commit_id,
+        )?)
+    }
+
+    /// Validate ledger structure, commit chain, and genesis invariants.
+    pub fn validate(&self, options: JsValue) -> JsResult<JsValue> {
+        let options = parse_validation_options(options)?;
+        to_js(&crate::validate_ledger(self.inner.ledger(), &options))
+    }
+
+    /// The committed ledger container as a plain object.
+    pub fn export(&self) -> JsResult<JsValue> {
+        to_js(self.inner.ledger())
+    }
+
+    /// The committed ledger as NDJSON, one record per line.
+    pub fn export_ndjson(&self) -> JsResult<String> {
+        crate::export_ledger_ndjson(self.inner.ledger())
+    }
+
+    /// The committed ledger container as deterministic CBOR.
+    pub fn export_cbor(&self) -> js_sys::Uint8Array {
+        js_sys::Uint8Array::from(crate::encode_ledger_cbor(self.inner.ledger()).as_slice())
+    }
+}
+
+/// Streaming import of `export_ledger_ndjson` output.
+///
+/// Feed text with `push` as it arrives (chunks may split lines), then call
+/// `finish` for the verified `Ledger`. Each record is checked as its line
+/// completes, so a bad archive fails early and the full text is never held
+/// in memory. A pruned ledger is only accepted when its checkpoint ID is
+/// passed to the constructor.
+#[wasm_bindgen]
+pub struct NdjsonReader {
+    inner: crate::NdjsonReader,
+}
+
+#[wasm_bindgen]
+impl NdjsonReader {
+    #[wasm_bindgen(constructor)]
+    pub fn new(pruned_base: Option<String>) -> NdjsonReader {
+        NdjsonReader {
+            inner: pruned_base.map_or_else(
+                crate::NdjsonReader::new,
+                crate::NdjsonReader::with_pruned_base,
+            ),
+        }
+    }
+
+    /// Lines read so far.
+    #[wasm_bindgen(getter)]
+    pub fn lines(&self) -> usize {
+        self.inner.lines_read()
+    }
+
+    /// Reads a chunk of text. Throws on the first invalid record.
+    pub fn push(&mut self, chunk: &str) -> JsResult<()> {
+        self.inner.push(chunk)
+    }
+
+    /// Reads the final line and returns the ledger. The reader cannot be
+    /// used afterwards.
+    pub fn finish(self) -> JsResult<Ledger> {
+        Ok(Ledger {
+            inner: StagedLedger::new(self.inner.finish()?),
+        })
+    }
+}
+
+fn js_error_string(value: &JsValue) -> String {
+    if let Some(message) = value.as_string() {
+        return message;
+    }
+    if let Some(error) = value.dyn_ref::<js_sys::Error>() {
+        return format!(
+            "{}: {}",
+            String::from(error.name()),
+            String::from(error.message())
+        );
+    }
+    format!("{:?}", value)
+}
+
+type SignatureResults = BTreeMap<String, Result<bool, String>>;
+
+/// Runs `options.verifyEntrySignature` over every epoch entry up front so the
+/// (possibly async) JS callback can be awaited before validation runs.
+async fn verify_epoch_signatures(
+    ledger: &LedgerContainer,
+    options: &JsValue,
+) -> JsResult<Option<SignatureResults>> {
+    if options.is_null() || options.is_undefined() {
+        return Ok(None);
+    }
+    let verify = js_sys::Reflect::get(options, &JsValue::from_str("verifyEntrySignature"))
+        .map_err(|error| ConcordError::Deserialization {
+            message: js_error_string(&error),
+        })?;
+    let verify = match verify.dyn_into::<js_sys::Function>() {
+        Ok(verify) => verify,
+        Err(_) => return Ok(None),
+    };
+    let mut results = SignatureResults::new();
+    for item in crate::get_epoch_chain(ledger)? {
+        let entry = to_js(&item.entry)?;
+        let outcome = match verify.call1(&JsValue::NULL, &entry) {
+            Ok(value) => match value.dyn_into::<js_sys::Promise>() {
+                Ok(promise) => wasm_bindgen_futures::JsFuture::from(promise).await,
+                Err(value) => Ok(value),
+            },
+            Err(error) => Err(error),
+        };
+        results.insert(
+            item.entry_id,
+            outcome
+                .map(|value| value.is_truthy())
+                .map_err(|error| js_error_string(&error)),
+        );
+    }
+    Ok(Some(results))
+}
+
+fn lookup_signature(results: &SignatureResults, entry_id: &str) -> Result<bool, String> {
+    results
+        .get(entry_id)
+        .cloned()
+        .unwrap_or_else(|| Err("signature was not checked".to_string()))
+}
+
+fn assertion_from_js(value: &JsValue) -> JsResult<Assertion> {
+    Assertion::from_value(&json_from_js(value)?)
+}
+
+/// Canonical signing payload for an assertion (excludes id, signature).
+#[wasm_bindgen]
+pub fn get_assertion_signing_payload(assertion: JsValue) -> JsResult<JsValue> {
+    let assertion = assertion_from_js(&assertion)?;
+    Ok(JsValue::from_str(&crate::get_assertion_signing_payload(
+        &assertion,
+    )?))
+}
+
+/// Canonical signing payload bytes for an assertion (excludes id, signature).
+#[wasm_bindgen]
+pub fn get_assertion_signing_bytes(assertion: JsValue) -> JsResult<JsValue> {
+    let assertion = assertion_from_js(&assertion)?;
+    let bytes = crate::get_assertion_signing_bytes(&assertion)?;
+    Ok(js_sys::Uint8Array::from(bytes.as_slice()).into())
+}
+
+/// Validate assertion shape and canonicalizability.
+#[wasm_bindgen]
+pub fn validate_assertion(assertion: JsValue) -> JsResult<JsValue> {
+    let result = match input_from_js(&assertion, &mut Vec::new()).into_json() {
+        Ok(assertion) => crate::validate_assertion(&assertion),
+        Err(error) => ValidationResult::from_errors(vec![ValidationError::new(
+            "INVALID_ASSERTION",
+            error.message(),
+        )]),
+    };
+    to_js(&result)
+}
+
+/// Strips all whitespace from an identity public key.
+#[wasm_bindgen]
+pub fn canonicalize_identity_key(value: String) -> String {
+    crate::canonicalize_identity_key(&value)
+}
+
+/// Normalizes line endings and trims an age recipient.
+#[wasm_bindgen]
+pub fn canonicalize_age_recipient(value: String) -> String {
+    crate::canonicalize_age_recipient(&value)
+}
+
+/// SHA-256 of the canonicalized identity key.
+#[wasm_bindgen]
+pub fn derive_signer_key_id(public_identity_key: String) -> String {
+    crate::derive_signer_key_id(&public_identity_key)
+}
+
+/// Deterministic EpochID from `{ signerKeyId, encryptionPublicKey, prevEpochId, createdAt }`.
+#[wasm_bindgen]
+pub fn derive_epoch_id(params: JsValue) -> JsResult<JsValue> {
+    let params: EpochIdParams = serde_wasm_bindgen::from_value(params)?;
+    Ok(JsValue::from_str(&crate::derive_epoch_id(&params)?))
+}
+
+/// Epoch entries in commit-chain order.
+#[wasm_bindgen]
+pub fn get_epoch_chain(ledger: JsValue) -> JsResult<JsValue> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    to_js(&crate::get_epoch_chain(&ledger)?)
+}
+
+/// Validate the epoch chain. `options.verifyEntrySignature` may return a
+/// boolean or a Promise of one.
+#[wasm_bindgen]
+pub async fn validate_ledger_epochs(ledger: JsValue, options: JsValue) -> JsResult<JsValue> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    let signatures = verify_epoch_signatures(&ledger, &options).await?;
+    let verify =
+        |entry_id: &str, _: &Entry| lookup_signature(signatures.as_ref().unwrap(), entry_id);
+    let options = EpochValidationOptions {
+        verify_entry_signature: signatures.as_ref().map(|_| &verify as _),
+    };
+    to_js(&crate::validate_ledger_epochs(&ledger, options)?)
+}
+
+/// The most recent epoch entry, once the epoch chain validates.
+#[wasm_bindgen]
+pub async fn get_active_epoch(ledger: JsValue, options: JsValue) -> JsResult<JsValue> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    let signatures = verify_epoch_signatures(&ledger, &options).await?;
+    let verify =
+        |entry_id: &str, _: &Entry| lookup_signature(signatures.as_ref().unwrap(), entry_id);
+    let options = EpochValidationOptions {
+        verify_entry_signature: signatures.as_ref().map(|_| &verify as _),
+    };
+    to_js(&crate::get_active_epoch(&ledger, options)?)
+}
+
+/// Checks that encrypted payloads reference an epoch declared earlier in the chain.
+#[wasm_bindgen]
+pub fn validate_ledger_encryption_key_ids(ledger: JsValue) -> JsResult<JsValue> {
+    let ledger: LedgerContainer = serde_wasm_bindgen::from_value(ledger)?;
+    to_js(&crate::validate_ledger_encryption_key_ids(&ledger)?)
+}
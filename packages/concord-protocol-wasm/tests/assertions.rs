@@ -0,0 +1,93 @@
+//! Ports the assertion signing payload cases from `protocol.test.js`.
+
+use concord_protocol_wasm::{
+    get_assertion_signing_bytes, get_assertion_signing_payload, validate_assertion, Assertion,
+};
+use serde_json::json;
+
+#[test]
+fn signing_payload_includes_payload_when_present() {
+    let assertion = Assertion::from_value(&json!({
+        "id": "assert-1",
+        "subject": { "kind": "decision", "id": "decision-123" },
+        "claim": "signed",
+        "payload": { "b": 2, "a": 1 },
+        "assertedBy": "did:example:alice",
+        "assertedAt": 1717352400000u64,
+        "signature": "sig"
+    }))
+    .unwrap();
+    let expected = r#"{"assertedAt":1717352400000,"assertedBy":"did:example:alice","claim":"signed","payload":{"a":1,"b":2},"subject":{"id":"decision-123","kind":"decision"}}"#;
+    assert_eq!(get_assertion_signing_payload(&assertion).unwrap(), expected);
+    assert_eq!(
+        get_assertion_signing_bytes(&assertion).unwrap(),
+        expected.as_bytes()
+    );
+}
+
+#[test]
+fn signing_payload_omits_payload_when_absent() {
+    let assertion = Assertion::from_value(&json!({
+        "id": "assert-2",
+        "subject": { "kind": "nfc-tag", "id": "tag-1" },
+        "claim": "found",
+        "assertedBy": "did:example:sam",
+        "assertedAt": 1717360000000u64,
+        "signature": "sig"
+    }))
+    .unwrap();
+    assert_eq!(
+        get_assertion_signing_payload(&assertion).unwrap(),
+        r#"{"assertedAt":1717360000000,"assertedBy":"did:example:sam","claim":"found","subject":{"id":"tag-1","kind":"nfc-tag"}}"#
+    );
+
+    let explicit_null = Assertion::from_value(&json!({
+        "subject": { "kind": "nfc-tag", "id": "tag-1" },
+        "claim": "found",
+        "payload": null,
+        "assertedBy": "did:example:sam",
+        "assertedAt": 1717360000000u64
+    }))
+    .unwrap();
+    assert!(get_assertion_signing_payload(&explicit_null)
+        .unwrap()
+        .contains(r#""payload":null"#));
+}
+
+#[test]
+fn shape_validation_reports_each_field() {
+    let result = validate_assertion(&json!({
+        "subject": { "kind": "", "id": 7 },
+        "claim": "",
+        "assertedBy": null,
+        "assertedAt": "2024-06-02T18:20:00Z"
+    }));
+    assert!(!result.ok);
+    let paths: Vec<_> = result
+        .errors
+        .iter()
+        .map(|error| (error.code, error.path.as_deref().unwrap()))
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            ("INVALID_ASSERTION", "subject.kind"),
+            ("INVALID_ASSERTION", "subject.id"),
+            ("INVALID_ASSERTION", "claim"),
+            ("INVALID_ASSERTION", "assertedBy"),
+            ("INVALID_ASSERTION", "assertedAt"),
+        ]
+    );
+    assert_eq!(
+        result.errors[4].message,
+        "Assertion.assertedAt must be a finite number"
+    );
+    assert_eq!(
+        validate_assertion(&json!({ "claim": "x" })).errors[0].message,
+        "Assertion.subject must be an object"
+    );
+
+    let error = Assertion::from_value(&json!([])).unwrap_err();
+    assert_eq!(error.code(), "INVALID_ASSERTION");
+    assert_eq!(error.message(), "Assertion must be an object");
+}
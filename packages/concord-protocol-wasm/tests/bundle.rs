@@ -0,0 +1,113 @@
+use concord_protocol_wasm::{
+    apply_bundle, create_bundle, create_ledger, diff_ledgers, get_replay_entries, validate_ledger,
+    LedgerContainer, ValidationOptions,
+};
+use serde_json::json;
+
+mod common;
+
+use common::{commit_entry, new_ledger};
+
+fn peers() -> (LedgerContainer, LedgerContainer) {
+    let mut behind = new_ledger();
+    commit_entry(&mut behind, "user-1", "2026-01-01T00:01:00Z");
+    let mut ahead = behind.clone();
+    commit_entry(&mut ahead, "user-2", "2026-01-01T00:02:00Z");
+    commit_entry(&mut ahead, "user-3", "2026-01-01T00:03:00Z");
+    (behind, ahead)
+}
+
+#[test]
+fn bundle_fast_forwards_a_peer() {
+    let (mut behind, ahead) = peers();
+    let bundle = create_bundle(&ahead, Some(&behind.head)).unwrap();
+    assert_eq!(bundle.base.as_deref(), Some(behind.head.as_str()));
+    assert_eq!(bundle.head, ahead.head);
+    assert_eq!(bundle.commits.len(), 2);
+    assert_eq!(bundle.entries.len(), 2);
+
+    let added = apply_bundle(&mut behind, &bundle).unwrap();
+    assert_eq!(
+        added,
+        bundle
+            .commits
+            .iter()
+            .map(|created| created.commit_id.clone())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(behind, ahead);
+    assert!(validate_ledger(&behind, &ValidationOptions::default()).ok);
+
+    // Re-applying is a no-op.
+    assert!(apply_bundle(&mut behind, &bundle).unwrap().is_empty());
+    assert_eq!(behind, ahead);
+}
+
+#[test]
+fn full_history_bundle_needs_a_shared_genesis() {
+    let (behind, ahead) = peers();
+    let bundle = create_bundle(&ahead, None).unwrap();
+    assert_eq!(bundle.commits.len(), 4);
+
+    let mut fresh = new_ledger();
+    apply_bundle(&mut fresh, &bundle).unwrap();
+    assert_eq!(
+        get_replay_entries(&fresh).unwrap(),
+        get_replay_entries(&ahead).unwrap()
+    );
+
+    let mut stranger = create_ledger(None, Some("2026-02-01T00:00:00Z".to_string())).unwrap();
+    let before = stranger.clone();
+    let error = apply_bundle(&mut stranger, &bundle).unwrap_err();
+    assert_eq!(error.code(), "UNRELATED_HISTORIES");
+    assert_eq!(stranger, before);
+
+    let error = create_bundle(&behind, Some(&ahead.head)).unwrap_err();
+    assert_eq!(error.code(), "CURSOR_NOT_ON_CHAIN");
+}
+
+#[test]
+fn tampered_bundles_are_rejected_without_changes() {
+    let (behind, ahead) = peers();
+    let bundle = create_bundle(&ahead, Some(&behind.head)).unwrap();
+
+    let mut tampered = bundle.clone();
+    tampered.entries.values_mut().next().unwrap().payload = Some(json!({ "id": "evil" }));
+    let mut ledger = behind.clone();
+    let error = apply_bundle(&mut ledger, &tampered).unwrap_err();
+    assert_eq!(error.code(), "ENTRY_ID_MISMATCH");
+    assert_eq!(ledger, behind);
+
+    let mut tampered = bundle.clone();
+    tampered.commits[1].commit.timestamp = "2026-01-01T00:09:00Z".to_string();
+    let error = apply_bundle(&mut ledger, &tampered).unwrap_err();
+    assert_eq!(error.code(), "COMMIT_ID_MISMATCH");
+    assert_eq!(ledger, behind);
+
+    let mut unlinked = bundle.clone();
+    let removed = unlinked.commits.remove(0);
+    unlinked.entries.remove(&removed.commit.entries[0]);
+    let error = apply_bundle(&mut ledger, &unlinked).unwrap_err();
+    assert_eq!(error.code(), "MISSING_COMMIT");
+    assert_eq!(ledger, behind);
+
+    let mut missing_entry = bundle.clone();
+    let entry_id = missing_entry.commits[1].commit.entries[0].clone();
+    missing_entry.entries.remove(&entry_id);
+    let error = apply_bundle(&mut ledger, &missing_entry).unwrap_err();
+    assert_eq!(error.code(), "MISSING_ENTRY");
+    assert_eq!(ledger, behind);
+}
+
+#[test]
+fn diverged_peer_cannot_fast_forward() {
+    let (mut behind, ahead) = peers();
+    commit_entry(&mut behind, "user-4", "2026-01-01T00:04:00Z");
+    let diff = diff_ledgers(&ahead, &behind).unwrap();
+    let bundle = create_bundle(&ahead, diff.last_common_commit.as_deref()).unwrap();
+
+    let before = behind.clone();
+    let error = apply_bundle(&mut behind, &bundle).unwrap_err();
+    assert_eq!(error.code(), "NOT_FAST_FORWARD");
+    assert_eq!(behind, before);
+}
@@ -0,0 +1,107 @@
+//! Cross-implementation checks for canonical number formatting. Expected
+//! strings were produced by `JSON.stringify` in Node.js, which is what the
+//! TypeScript `canonicalStringify` uses.
+
+use concord_protocol_wasm::{canonical_stringify, derive_entry_id, Entry};
+use serde_json::{json, Value};
+
+fn canonical(source: &str) -> String {
+    let value: Value = serde_json::from_str(source).unwrap();
+    canonical_stringify(&value).unwrap()
+}
+
+#[test]
+fn integers_match_ecmascript() {
+    assert_eq!(canonical("0"), "0");
+    assert_eq!(canonical("-0"), "0");
+    assert_eq!(canonical("-0.0"), "0");
+    assert_eq!(canonical("1.0"), "1");
+    assert_eq!(canonical("100"), "100");
+    assert_eq!(canonical("-42"), "-42");
+    assert_eq!(canonical("1E+2"), "100");
+    assert_eq!(canonical("1e20"), "100000000000000000000");
+    assert_eq!(canonical("1e21"), "1e+21");
+}
+
+#[test]
+fn unsafe_integers_round_like_ecmascript() {
+    assert_eq!(
+        canonical(
+            "[9007199254740993,18446744073709551615,-9223372036854775807,123456789012345678901234567890]"
+        ),
+        "[9007199254740992,18446744073709552000,-9223372036854776000,1.2345678901234568e+29]"
+    );
+}
+
+#[test]
+fn fractions_and_exponents_match_ecmascript() {
+    assert_eq!(canonical("0.1"), "0.1");
+    assert_eq!(canonical("1.50"), "1.5");
+    assert_eq!(canonical("0.000001"), "0.000001");
+    assert_eq!(canonical("1e-7"), "1e-7");
+    assert_eq!(canonical("-1e-7"), "-1e-7");
+    assert_eq!(canonical("1.2e-6"), "0.0000012");
+    assert_eq!(
+        canonical(r#"{"b":1.0,"a":[1e21,-0.0,100,1.50,2e-7]}"#),
+        r#"{"a":[1e+21,0,100,1.5,2e-7],"b":1}"#
+    );
+}
+
+#[test]
+fn f64_bit_patterns_match_ecmascript() {
+    let vectors: &[(u64, &str)] = &[
+        (0x444b1ae4d6e2ef50, "1e+21"),
+        (0x3e7ad7f29abcaf48, "1e-7"),
+        (0x3c36b082c2148b8e, "1.23e-18"),
+        (0x3fd3333333333334, "0.30000000000000004"),
+        (0xbe8421f5f40d8376, "-1.5e-7"),
+        (0x7fefffffffffffff, "1.7976931348623157e+308"),
+        (0x0000000000000001, "5e-324"),
+        (0x0010000000000000, "2.2250738585072014e-308"),
+        (0x4011666666666666, "4.35"),
+        (0x3fd5555555555555, "0.3333333333333333"),
+        (0xbfe5555555555555, "-0.6666666666666666"),
+        (0x400921fb54442d18, "3.141592653589793"),
+        (0x7e41eb2d66005835, "1.5e+300"),
+        (0x43118b54f2236200, "1234567890000000"),
+        (0x3f201f31f46ed246, "0.000123"),
+        (0x444b1ae4d6e2ef4f, "999999999999999900000"),
+        (0x3eb0c6f7a0b5ed8d, "0.000001"),
+        (0x0000419e21ac75e2, "3.56455644192673e-310"),
+        (0x60ab95e3756a1854, "4.7342222384734546e+157"),
+        (0x4429314b380db590, "232360298102691200000"),
+        (0x0604ce26adca13fc, "1.1461660851762668e-279"),
+        (0x56e850ddef48089a, "4.5685515821338507e+110"),
+        (0x311075c484f878aa, "2.3290116935482593e-72"),
+        (0x6a4fc6e908d9b7c4, "1.2453742581165949e+204"),
+        (0x06d6f9b8879a6e8e, "1.0368798186641696e-275"),
+        (0x55dd7c0f01f88222, "4.2264311540882517e+105"),
+        (0x310da11f111c92a8, "2.0962043690032783e-72"),
+        (0x35698029afd05622, "2.1299125070430454e-51"),
+        (0x4b5a29baee31160a, "1.0023673496925992e+55"),
+    ];
+    for (bits, expected) in vectors {
+        let value = json!(f64::from_bits(*bits));
+        assert_eq!(
+            &canonical_stringify(&value).unwrap(),
+            expected,
+            "bits {:#x}",
+            bits
+        );
+    }
+}
+
+#[test]
+fn entry_id_matches_typescript_for_float_payloads() {
+    let entry = Entry {
+        kind: "concord/test".to_string(),
+        timestamp: "2026-01-01T00:00:00Z".to_string(),
+        author: "author-1".to_string(),
+        payload: Some(serde_json::from_str(r#"{"amount":1.5e21,"ratio":1.0}"#).unwrap()),
+        signature: None,
+    };
+    assert_eq!(
+        derive_entry_id(&entry).unwrap(),
+        "09b81e37e6681847aad87c20c42bdddfd91c34997f207c9eec8789f85895376c"
+    );
+}
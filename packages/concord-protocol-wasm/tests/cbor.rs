@@ -0,0 +1,154 @@
+use concord_protocol_wasm::{
+    append_commit_strict, append_entry, create_commit, create_ledger, decode_ledger_cbor,
+    derive_commit_id, derive_entry_id, encode_ledger_cbor, merge_ledgers, validate_ledger,
+    CommitParams, Entry, LedgerContainer, ValidationOptions,
+};
+use serde_json::json;
+
+fn entry(payload: serde_json::Value) -> Entry {
+    Entry {
+        kind: "concord/user/added".to_string(),
+        timestamp: "2026-01-01T00:00:00Z".to_string(),
+        author: "author-1".to_string(),
+        payload: Some(payload),
+        signature: None,
+    }
+}
+
+fn commit(ledger: &mut LedgerContainer, entries: Vec<Entry>, timestamp: &str) {
+    let entries = entries
+        .into_iter()
+        .map(|entry| append_entry(ledger, entry).unwrap())
+        .collect();
+    let created = create_commit(
+        ledger,
+        CommitParams {
+            entries,
+            metadata: Some(json!({ "message": "sync", "count": 2 })),
+            timestamp: Some(timestamp.to_string()),
+            ..CommitParams::default()
+        },
+    )
+    .unwrap();
+    append_commit_strict(ledger, created.commit_id, created.commit).unwrap();
+}
+
+fn sample_ledger() -> LedgerContainer {
+    let mut ledger = create_ledger(
+        Some(json!({ "name": "cbor" })),
+        Some("2026-01-01T00:00:00Z".to_string()),
+    )
+    .unwrap();
+    let mut other = ledger.clone();
+    commit(
+        &mut ledger,
+        vec![
+            entry(
+                json!({ "id": "user-1", "score": 1.5, "whole": 1.0, "big": 9007199254740993u64 }),
+            ),
+            entry(json!({ "id": "user-2", "tags": ["a", "b"], "nested": { "z": null, "a": -42 } })),
+            Entry {
+                payload: None,
+                signature: Some("c2lnbmF0dXJl".to_string()),
+                ..entry(json!(null))
+            },
+        ],
+        "2026-01-01T00:01:00Z",
+    );
+    commit(
+        &mut other,
+        vec![entry(json!({ "id": "user-3", "emoji": "🦀" }))],
+        "2026-01-01T00:02:00Z",
+    );
+    let mut merged = merge_ledgers(
+        &ledger,
+        &other,
+        None,
+        Some("2026-01-01T00:03:00Z".to_string()),
+    )
+    .unwrap();
+    let head = merged.head.clone();
+    merged.commits.get_mut(&head).unwrap().signature = Some("c2VhbA".to_string());
+    merged
+}
+
+#[test]
+fn round_trips_losslessly() {
+    let ledger = sample_ledger();
+    let bytes = encode_ledger_cbor(&ledger);
+    let decoded = decode_ledger_cbor(&bytes).unwrap();
+    let reparsed: LedgerContainer =
+        serde_json::from_str(&serde_json::to_string(&ledger).unwrap()).unwrap();
+    assert_eq!(decoded, reparsed);
+    assert_eq!(
+        serde_json::to_string(&decoded).unwrap(),
+        serde_json::to_string(&ledger).unwrap()
+    );
+
+    // IDs are still derived from canonical JSON.
+    for (commit_id, commit) in &decoded.commits {
+        assert_eq!(&derive_commit_id(commit).unwrap(), commit_id);
+    }
+    for (entry_id, entry) in &decoded.entries {
+        assert_eq!(&derive_entry_id(entry).unwrap(), entry_id);
+    }
+    assert!(validate_ledger(&decoded, &ValidationOptions::default()).ok);
+}
+
+#[test]
+fn encoding_is_deterministic_and_compact() {
+    let ledger = sample_ledger();
+    let bytes = encode_ledger_cbor(&ledger);
+    let reparsed: LedgerContainer =
+        serde_json::from_str(&serde_json::to_string_pretty(&ledger).unwrap()).unwrap();
+    assert_eq!(encode_ledger_cbor(&reparsed), bytes);
+
+    // IDs are raw 32-byte strings (major type 2, length 32), never hex text.
+    let head: Vec<u8> = (0..32)
+        .map(|index| u8::from_str_radix(&ledger.head[index * 2..index * 2 + 2], 16).unwrap())
+        .collect();
+    let mut tagged = vec![0x58, 0x20];
+    tagged.extend(&head);
+    assert!(bytes.windows(34).any(|window| window == tagged.as_slice()));
+    assert!(!bytes
+        .windows(64)
+        .any(|window| window == ledger.head.as_bytes()));
+
+    let json = serde_json::to_vec(&ledger).unwrap();
+    assert!(
+        bytes.len() * 3 < json.len() * 2,
+        "{} vs {}",
+        bytes.len(),
+        json.len()
+    );
+}
+
+#[test]
+fn keeps_non_hash_ids_and_rejects_malformed_input() {
+    let mut ledger = create_ledger(None, Some("2026-01-01T00:00:00Z".to_string())).unwrap();
+    let genesis_id = ledger.head.clone();
+    ledger
+        .commits
+        .get_mut(&genesis_id)
+        .unwrap()
+        .entries
+        .push("not-a-hash".to_string());
+    ledger.head = "ABCDEF".to_string();
+    assert_eq!(
+        decode_ledger_cbor(&encode_ledger_cbor(&ledger)).unwrap(),
+        ledger
+    );
+
+    let error = decode_ledger_cbor(&[0xff, 0x00]).unwrap_err();
+    assert_eq!(error.code(), "INVALID_CBOR");
+
+    let mut bytes = Vec::new();
+    ciborium::into_writer(
+        &json!({ "format": "concord-ledger", "version": "1.0", "head": "x", "commits": {}, "entries": {}, "extra": 1 }),
+        &mut bytes,
+    )
+    .unwrap();
+    let error = decode_ledger_cbor(&bytes).unwrap_err();
+    assert_eq!(error.code(), "INVALID_CBOR");
+    assert_eq!(error.message(), "Ledger.extra is not a known field");
+}
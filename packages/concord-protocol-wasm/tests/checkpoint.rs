@@ -0,0 +1,223 @@
+use concord_protocol_wasm::{
+    append_commit, append_commit_strict, append_entry, create_checkpoint_commit,
+    decode_ledger_cbor, encode_ledger_cbor, export_ledger_ndjson, get_checkpoint, get_commit_chain,
+    get_replay_entries, hash_data, is_checkpoint_commit, merge_ledgers, prune_ledger,
+    validate_ledger, CommitParams, LedgerContainer, NdjsonReader, ValidationOptions,
+};
+use serde_json::json;
+
+mod common;
+
+use common::{commit, commit_entry, entry, new_ledger};
+
+fn checkpoint(ledger: &mut LedgerContainer) -> String {
+    let state = get_replay_entries(ledger).unwrap();
+    let state_hash = hash_data(&serde_json::to_value(state).unwrap()).unwrap();
+    let created = create_checkpoint_commit(
+        ledger,
+        state_hash,
+        Some(json!({ "note": "nightly" })),
+        Some("2026-01-01T00:01:00Z".to_string()),
+    )
+    .unwrap();
+    append_commit_strict(ledger, created.commit_id.clone(), created.commit).unwrap();
+    created.commit_id
+}
+
+fn is_valid(ledger: &LedgerContainer, pruned_base: Option<&String>) -> bool {
+    let options = ValidationOptions {
+        pruned_base: pruned_base.cloned(),
+        ..ValidationOptions::default()
+    };
+    let result = validate_ledger(ledger, &options);
+    assert!(result.ok, "{:?}", result.errors);
+    result.ok
+}
+
+fn checkpointed_ledger() -> (LedgerContainer, String) {
+    let mut ledger = new_ledger();
+    for id in ["user-1", "user-2", "user-3"] {
+        commit_entry(&mut ledger, id, "2026-01-01T00:01:00Z");
+    }
+    let checkpoint_id = checkpoint(&mut ledger);
+    commit_entry(&mut ledger, "user-4", "2026-01-01T00:01:00Z");
+    (ledger, checkpoint_id)
+}
+
+#[test]
+fn checkpoints_record_height_and_validate() {
+    let (mut ledger, checkpoint_id) = checkpointed_ledger();
+
+    // Callers' own `checkpoint` metadata does not make a checkpoint.
+    let mut own = ledger.clone();
+    let entry_id = append_entry(&mut own, entry("user-5")).unwrap();
+    let plain = commit(
+        &mut own,
+        CommitParams {
+            entries: vec![entry_id],
+            metadata: Some(json!({ "checkpoint": { "height": 99 } })),
+            ..CommitParams::default()
+        },
+    );
+    assert!(!is_checkpoint_commit(&own.commits[&plain]));
+    assert!(is_valid(&own, None));
+
+    let commit = &ledger.commits[&checkpoint_id];
+    let recorded = get_checkpoint(commit).unwrap();
+    assert_eq!(recorded.height, 4);
+    assert_eq!(commit.metadata.as_ref().unwrap()["note"], json!("nightly"));
+    assert!(is_valid(&ledger, None));
+
+    let mut forged = commit.clone();
+    forged.metadata.as_mut().unwrap()["concord/checkpoint"]["height"] = json!(9);
+    ledger.commits.insert(checkpoint_id, forged);
+    let result = validate_ledger(&ledger, &ValidationOptions::default());
+    assert!(result
+        .errors
+        .iter()
+        .any(|error| error.code == "INVALID_CHECKPOINT"
+            && error.path.as_deref() == Some(r#"metadata["concord/checkpoint"].height"#)));
+}
+
+#[test]
+fn pruned_ledger_starts_at_the_checkpoint() {
+    let (ledger, checkpoint_id) = checkpointed_ledger();
+    let pruned = prune_ledger(&ledger, &checkpoint_id).unwrap();
+    assert_eq!(pruned.commits.len(), 2);
+    assert_eq!(pruned.entries.len(), 1);
+    assert!(is_valid(&pruned, Some(&checkpoint_id)));
+    assert_eq!(get_commit_chain(&pruned).unwrap()[0], checkpoint_id);
+    assert_eq!(get_replay_entries(&pruned).unwrap(), vec![entry("user-4")]);
+
+    // Later checkpoints count up from the pruned base.
+    let mut grown = pruned.clone();
+    let next = checkpoint(&mut grown);
+    assert_eq!(get_checkpoint(&grown.commits[&next]).unwrap().height, 6);
+    assert!(is_valid(&grown, Some(&checkpoint_id)));
+    let repruned = prune_ledger(&grown, &next).unwrap();
+    assert_eq!(repruned.commits.len(), 1);
+    assert!(is_valid(&repruned, Some(&next)));
+
+    let decoded = decode_ledger_cbor(&encode_ledger_cbor(&pruned)).unwrap();
+    assert_eq!(
+        serde_json::to_value(decoded).unwrap(),
+        serde_json::to_value(&pruned).unwrap()
+    );
+    let mut reader = NdjsonReader::with_pruned_base(checkpoint_id.clone());
+    reader
+        .push(&export_ledger_ndjson(&pruned).unwrap())
+        .unwrap();
+    let read = reader.finish().unwrap();
+    assert_eq!(read.head, pruned.head);
+    assert_eq!(
+        read.commits.keys().collect::<Vec<_>>(),
+        pruned.commits.keys().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn prune_requires_a_checkpoint_that_cuts_history() {
+    let (mut ledger, checkpoint_id) = checkpointed_ledger();
+    let error = prune_ledger(&ledger, &ledger.head).unwrap_err();
+    assert_eq!(error.code(), "INVALID_CHECKPOINT");
+    let error = prune_ledger(&ledger, &"f".repeat(64)).unwrap_err();
+    assert_eq!(error.code(), "CURSOR_NOT_ON_CHAIN");
+
+    // A branch forked before the checkpoint and merged after it still
+    // needs the history behind the checkpoint.
+    let genesis = get_commit_chain(&ledger).unwrap()[0].clone();
+    let mut branch = ledger.clone();
+    branch.head = genesis;
+    commit_entry(&mut branch, "side", "2026-01-01T00:01:00Z");
+    let merged = merge_ledgers(
+        &ledger,
+        &branch,
+        None,
+        Some("2026-01-01T00:03:00Z".to_string()),
+    )
+    .unwrap();
+    let error = prune_ledger(&merged, &checkpoint_id).unwrap_err();
+    assert_eq!(error.code(), "INVALID_CHECKPOINT");
+
+    // A checkpoint must not carry entries.
+    let entry_id = append_entry(&mut ledger, entry("x")).unwrap();
+    let mut bad = ledger.commits[&checkpoint_id].clone();
+    bad.entries = vec![entry_id];
+    let error = append_commit(&mut ledger, "id".to_string(), bad).unwrap_err();
+    assert_eq!(error.code(), "INVALID_COMMIT");
+}
+
+#[test]
+fn a_pruned_base_must_be_expected() {
+    let (ledger, checkpoint_id) = checkpointed_ledger();
+    let pruned = prune_ledger(&ledger, &checkpoint_id).unwrap();
+    let missing = |options: &ValidationOptions| {
+        let result = validate_ledger(&pruned, options);
+        result.errors.len() == 1
+            && result.errors[0].code == "MISSING_COMMIT"
+            && result.errors[0].commit_id.as_deref() == Some(checkpoint_id.as_str())
+    };
+    assert!(missing(&ValidationOptions::default()));
+    assert!(missing(&ValidationOptions {
+        pruned_base: Some(ledger.head.clone()),
+        ..ValidationOptions::default()
+    }));
+    let ndjson = export_ledger_ndjson(&pruned).unwrap();
+    let rejected = |mut reader: NdjsonReader| {
+        let error = reader.push(&ndjson).unwrap_err();
+        error.code() == "INVALID_NDJSON" && error.context()["line"] == json!(2)
+    };
+    assert!(rejected(NdjsonReader::new()));
+    assert!(rejected(NdjsonReader::with_pruned_base(
+        ledger.head.clone()
+    )));
+
+    // Truncated history is not a pruned base either, whatever it claims.
+    let mut truncated = ledger.clone();
+    let chain = get_commit_chain(&ledger).unwrap();
+    truncated.commits.remove(&chain[0]);
+    truncated.commits.remove(&chain[1]);
+    let result = validate_ledger(&truncated, &ValidationOptions::default());
+    assert!(result
+        .errors
+        .iter()
+        .any(|error| error.code == "MISSING_COMMIT"));
+}
+
+#[test]
+fn checkpoint_heights_follow_merged_branches() {
+    let (ledger, _) = checkpointed_ledger();
+    let genesis = get_commit_chain(&ledger).unwrap()[0].clone();
+    let mut branch = ledger.clone();
+    branch.head = genesis;
+    commit_entry(&mut branch, "side", "2026-01-01T00:01:00Z");
+    let side = checkpoint(&mut branch);
+    assert_eq!(get_checkpoint(&branch.commits[&side]).unwrap().height, 2);
+
+    let mut merged = merge_ledgers(
+        &ledger,
+        &branch,
+        None,
+        Some("2026-01-01T00:01:00Z".to_string()),
+    )
+    .unwrap();
+    // Genesis, user-1..3, the first checkpoint, user-4, side, the side
+    // checkpoint and the merge.
+    let last = checkpoint(&mut merged);
+    assert_eq!(get_checkpoint(&merged.commits[&last]).unwrap().height, 9);
+    assert!(is_valid(&merged, None));
+
+    merged
+        .commits
+        .get_mut(&side)
+        .unwrap()
+        .metadata
+        .as_mut()
+        .unwrap()["concord/checkpoint"]["height"] = json!(3);
+    let result = validate_ledger(&merged, &ValidationOptions::default());
+    assert!(result
+        .errors
+        .iter()
+        .any(|error| error.code == "INVALID_CHECKPOINT"
+            && error.commit_id.as_deref() == Some(side.as_str())));
+}
@@ -0,0 +1,60 @@
+//! Fixtures shared by the integration tests. Each test crate uses a subset.
+
+#![allow(dead_code)]
+
+use concord_protocol_wasm::{
+    append_commit_strict, append_entry, create_commit, create_ledger, derive_entry_id,
+    CommitParams, Entry, LedgerContainer,
+};
+use serde_json::json;
+
+/// A `concord/user/added` entry for user `id`.
+pub fn entry(id: &str) -> Entry {
+    Entry {
+        kind: "concord/user/added".to_string(),
+        timestamp: "2026-01-01T00:00:00Z".to_string(),
+        author: "author-1".to_string(),
+        payload: Some(json!({ "id": id })),
+        signature: None,
+    }
+}
+
+/// A new ledger whose genesis commit is timestamped 2026-01-01T00:00:00Z.
+pub fn new_ledger() -> LedgerContainer {
+    create_ledger(None, Some("2026-01-01T00:00:00Z".to_string())).unwrap()
+}
+
+/// Creates a commit on head from `params` and appends it strictly.
+pub fn commit(ledger: &mut LedgerContainer, params: CommitParams) -> String {
+    let created = create_commit(ledger, params).unwrap();
+    append_commit_strict(ledger, created.commit_id.clone(), created.commit).unwrap();
+    created.commit_id
+}
+
+/// Commits `entry(id)` for each of `ids`. Entries already in the ledger are
+/// listed again without being re-appended.
+pub fn commit_entries(ledger: &mut LedgerContainer, ids: &[&str], timestamp: &str) -> String {
+    let entries = ids
+        .iter()
+        .map(|id| {
+            let entry_id = derive_entry_id(&entry(id)).unwrap();
+            if !ledger.entries.contains_key(&entry_id) {
+                append_entry(ledger, entry(id)).unwrap();
+            }
+            entry_id
+        })
+        .collect();
+    commit(
+        ledger,
+        CommitParams {
+            entries,
+            timestamp: Some(timestamp.to_string()),
+            ..CommitParams::default()
+        },
+    )
+}
+
+/// Commits `entry(id)` alone.
+pub fn commit_entry(ledger: &mut LedgerContainer, id: &str, timestamp: &str) -> String {
+    commit_entries(ledger, &[id], timestamp)
+}
@@ -0,0 +1,138 @@
+use concord_protocol_wasm::{
+    append_entry, create_ledger, get_commit_chain, validate_commit, validate_entry,
+    validate_ledger, Commit, Entry, ValidationOptions, VALIDATION_ERROR_CODES,
+};
+use serde_json::json;
+
+fn codes_are_known(errors: &[concord_protocol_wasm::ValidationError]) {
+    for error in errors {
+        assert!(VALIDATION_ERROR_CODES.contains(&error.code), "{:?}", error);
+    }
+}
+
+#[test]
+fn diagnostics_serialize_like_epoch_errors() {
+    let mut ledger = create_ledger(None, Some("2026-01-01T00:00:00Z".to_string())).unwrap();
+    let genesis_id = ledger.head.clone();
+    let genesis = ledger.commits.get_mut(&genesis_id).unwrap();
+    genesis.entries.push("missing".to_string());
+
+    let result = validate_ledger(&ledger, &ValidationOptions::default());
+    codes_are_known(&result.errors);
+    let errors = serde_json::to_value(&result).unwrap()["errors"].clone();
+    assert_eq!(
+        errors,
+        json!([
+            {
+                "code": "GENESIS_INVALID",
+                "message": "Genesis commit entries must be an empty array",
+                "commitId": genesis_id,
+                "path": "entries"
+            },
+            {
+                "code": "COMMIT_ID_MISMATCH",
+                "message": errors[1]["message"],
+                "commitId": genesis_id
+            },
+            {
+                "code": "MISSING_ENTRY",
+                "message": format!("Commit {} references missing entry missing", genesis_id),
+                "commitId": genesis_id,
+                "entryId": "missing",
+                "path": "entries[0]"
+            }
+        ])
+    );
+}
+
+#[test]
+fn shape_validators_report_codes_and_paths() {
+    let entry = Entry {
+        kind: String::new(),
+        timestamp: "2026-01-01T00:00:00Z".to_string(),
+        author: String::new(),
+        payload: None,
+        signature: None,
+    };
+    let result = validate_entry(&entry, &ValidationOptions::default());
+    codes_are_known(&result.errors);
+    let found: Vec<_> = result
+        .errors
+        .iter()
+        .map(|error| (error.code, error.path.as_deref()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            ("INVALID_ENTRY", Some("kind")),
+            ("INVALID_ENTRY", Some("author"))
+        ]
+    );
+
+    let commit = Commit {
+        parent: Some(String::new()),
+        merge_parents: vec![],
+        timestamp: String::new(),
+        metadata: Some(json!([])),
+        entries: vec![],
+        entries_root: None,
+        signature: None,
+    };
+    let result = validate_commit(&commit, &ValidationOptions::default());
+    codes_are_known(&result.errors);
+    let paths: Vec<_> = result
+        .errors
+        .iter()
+        .map(|error| error.path.as_deref().unwrap())
+        .collect();
+    assert_eq!(paths, vec!["timestamp", "parent", "metadata"]);
+    assert!(result
+        .errors
+        .iter()
+        .all(|error| error.code == "INVALID_COMMIT"));
+}
+
+#[test]
+fn ledger_level_codes() {
+    let mut ledger = create_ledger(None, Some("2026-01-01T00:00:00Z".to_string())).unwrap();
+    ledger.format = "other".to_string();
+    ledger.head = String::new();
+    let result = validate_ledger(&ledger, &ValidationOptions::default());
+    let codes: Vec<_> = result.errors.iter().map(|error| error.code).collect();
+    assert_eq!(codes, vec!["LEDGER_FORMAT_INVALID", "LEDGER_HEAD_INVALID"]);
+
+    let mut ledger = create_ledger(None, Some("2026-01-01T00:00:00Z".to_string())).unwrap();
+    ledger.head = "nope".to_string();
+    let result = validate_ledger(&ledger, &ValidationOptions::default());
+    codes_are_known(&result.errors);
+    let codes: Vec<_> = result.errors.iter().map(|error| error.code).collect();
+    assert_eq!(codes, vec!["MISSING_HEAD", "MISSING_HEAD"]);
+}
+
+#[test]
+fn errors_carry_code_and_context() {
+    let mut ledger = create_ledger(None, Some("2026-01-01T00:00:00Z".to_string())).unwrap();
+    ledger.head = "missing".to_string();
+    let error = get_commit_chain(&ledger).unwrap_err();
+    assert_eq!(error.code(), "MISSING_HEAD");
+    assert_eq!(error.context().get("head"), Some(&json!("missing")));
+    assert_eq!(
+        error.to_string(),
+        "MISSING_HEAD: Missing head commit missing"
+    );
+
+    // A rejected append carries the same code as its diagnostics.
+    let error = append_entry(
+        &mut ledger,
+        Entry {
+            kind: String::new(),
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            author: "author-1".to_string(),
+            payload: None,
+            signature: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error.code(), "INVALID_ENTRY");
+    assert_eq!(error.context()["errors"][0]["code"], json!("INVALID_ENTRY"));
+}
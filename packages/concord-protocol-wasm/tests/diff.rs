@@ -0,0 +1,79 @@
+use concord_protocol_wasm::{
+    create_ledger, derive_entry_id, diff_ledgers, merge_ledgers, HeadRelation, LedgerContainer,
+};
+
+mod common;
+
+use common::{commit_entry, entry, new_ledger};
+
+fn base() -> (LedgerContainer, String) {
+    let mut ledger = new_ledger();
+    let shared = commit_entry(&mut ledger, "user-1", "2026-01-01T00:01:00Z");
+    (ledger, shared)
+}
+
+#[test]
+fn identical_ledgers_have_an_empty_diff() {
+    let (ledger, shared) = base();
+    let diff = diff_ledgers(&ledger, &ledger.clone()).unwrap();
+    assert_eq!(diff.relation, HeadRelation::Equal);
+    assert_eq!(diff.last_common_commit, Some(shared));
+    assert!(diff.only_in_a.commits.is_empty() && diff.only_in_a.entries.is_empty());
+    assert!(diff.only_in_b.commits.is_empty() && diff.only_in_b.entries.is_empty());
+}
+
+#[test]
+fn fast_forward_is_reported_from_both_sides() {
+    let (behind, shared) = base();
+    let mut ahead = behind.clone();
+    let second = commit_entry(&mut ahead, "user-2", "2026-01-01T00:02:00Z");
+    let third = commit_entry(&mut ahead, "user-3", "2026-01-01T00:03:00Z");
+
+    let diff = diff_ledgers(&ahead, &behind).unwrap();
+    assert_eq!(diff.relation, HeadRelation::Ahead);
+    assert_eq!(diff.last_common_commit.as_deref(), Some(shared.as_str()));
+    assert_eq!(diff.only_in_a.commits, vec![second, third]);
+    let mut entries = vec![
+        derive_entry_id(&entry("user-2")).unwrap(),
+        derive_entry_id(&entry("user-3")).unwrap(),
+    ];
+    entries.sort();
+    assert_eq!(diff.only_in_a.entries, entries);
+    assert!(diff.only_in_b.commits.is_empty());
+
+    assert_eq!(
+        diff_ledgers(&behind, &ahead).unwrap().relation,
+        HeadRelation::Behind
+    );
+}
+
+#[test]
+fn diverged_and_unrelated_heads() {
+    let (mut laptop, shared) = base();
+    let mut phone = laptop.clone();
+    let on_laptop = commit_entry(&mut laptop, "user-2", "2026-01-01T00:02:00Z");
+    let on_phone = commit_entry(&mut phone, "user-3", "2026-01-01T00:03:00Z");
+
+    let diff = diff_ledgers(&laptop, &phone).unwrap();
+    assert_eq!(diff.relation, HeadRelation::Diverged);
+    assert_eq!(diff.last_common_commit, Some(shared));
+    assert_eq!(diff.only_in_a.commits, vec![on_laptop.clone()]);
+    assert_eq!(diff.only_in_b.commits, vec![on_phone]);
+
+    let merged = merge_ledgers(&laptop, &phone, None, None).unwrap();
+    let diff = diff_ledgers(&merged, &laptop).unwrap();
+    assert_eq!(diff.relation, HeadRelation::Ahead);
+    assert_eq!(diff.last_common_commit, Some(on_laptop));
+    assert_eq!(diff.only_in_a.commits.len(), 2);
+
+    let other = create_ledger(None, Some("2026-02-01T00:00:00Z".to_string())).unwrap();
+    let diff = diff_ledgers(&laptop, &other).unwrap();
+    assert_eq!(diff.relation, HeadRelation::Unrelated);
+    assert_eq!(diff.last_common_commit, None);
+    assert_eq!(diff.only_in_b.commits, vec![other.head.clone()]);
+
+    let json = serde_json::to_value(&diff).unwrap();
+    assert_eq!(json["relation"], "unrelated");
+    assert!(json["lastCommonCommit"].is_null());
+    assert!(json["onlyInA"]["commits"].is_array());
+}
@@ -0,0 +1,159 @@
+use concord_protocol_wasm::{
+    append_commit, append_commit_strict, append_entry, create_commit, derive_entry_id,
+    merge_ledgers, validate_commit, validate_ledger, CommitParams, LedgerContainer,
+    ValidationOptions,
+};
+use serde_json::json;
+
+mod common;
+
+use common::{commit, entry, new_ledger};
+
+fn params(entries: Vec<String>, strict: bool) -> CommitParams {
+    CommitParams {
+        entries,
+        timestamp: Some("2026-01-01T00:01:00Z".to_string()),
+        strict,
+        ..CommitParams::default()
+    }
+}
+
+fn ledger_with(ids: &[&str]) -> (LedgerContainer, Vec<String>) {
+    let mut ledger = new_ledger();
+    let entry_ids = ids
+        .iter()
+        .map(|id| append_entry(&mut ledger, entry(id)).unwrap())
+        .collect();
+    (ledger, entry_ids)
+}
+
+#[test]
+fn rejects_an_entry_repeated_in_one_commit() {
+    let (mut ledger, ids) = ledger_with(&["user-1", "user-2"]);
+    let entries = vec![ids[0].clone(), ids[1].clone(), ids[0].clone()];
+
+    let error = create_commit(&ledger, params(entries.clone(), true)).unwrap_err();
+    assert_eq!(error.code(), "INVALID_COMMIT");
+
+    let created = create_commit(&ledger, params(entries, false)).unwrap();
+    let result = validate_commit(&created.commit, &ValidationOptions::default());
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].code, "DUPLICATE_ENTRY_IN_COMMIT");
+    assert_eq!(result.errors[0].path.as_deref(), Some("entries[2]"));
+    assert_eq!(result.errors[0].entry_id.as_deref(), Some(ids[0].as_str()));
+
+    let error = append_commit(&mut ledger, created.commit_id, created.commit).unwrap_err();
+    assert_eq!(error.code(), "INVALID_COMMIT");
+    assert_eq!(
+        error.context()["errors"][0]["code"],
+        json!("DUPLICATE_ENTRY_IN_COMMIT")
+    );
+}
+
+#[test]
+fn rejects_an_entry_committed_earlier_on_the_chain() {
+    let (mut ledger, ids) = ledger_with(&["user-1", "user-2"]);
+    let first = commit(&mut ledger, params(vec![ids[0].clone()], true));
+    commit(&mut ledger, params(vec![ids[1].clone()], true));
+
+    let error = create_commit(&ledger, params(vec![ids[0].clone()], true)).unwrap_err();
+    assert_eq!(error.code(), "ENTRY_ALREADY_COMMITTED");
+    assert_eq!(error.context()["commitId"], json!(first));
+    assert_eq!(error.context()["entryId"], json!(ids[0]));
+
+    let created = create_commit(&ledger, params(vec![ids[0].clone()], false)).unwrap();
+    let mut forged = ledger.clone();
+    let error = append_commit(
+        &mut forged,
+        created.commit_id.clone(),
+        created.commit.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error.code(), "ENTRY_ALREADY_COMMITTED");
+    assert_eq!(forged, ledger);
+
+    // A container read back from JSON carries no index; it is rebuilt.
+    let mut decoded: LedgerContainer =
+        serde_json::from_value(serde_json::to_value(&ledger).unwrap()).unwrap();
+    let error = append_commit(
+        &mut decoded,
+        created.commit_id.clone(),
+        created.commit.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error.code(), "ENTRY_ALREADY_COMMITTED");
+
+    // Replacing a commit by hand leaves the count alone but still
+    // invalidates the index.
+    let (mut replaced, ids) = ledger_with(&["user-1"]);
+    let replacement = create_commit(
+        &replaced,
+        CommitParams {
+            timestamp: Some("2026-01-01T00:02:00Z".to_string()),
+            ..params(vec![ids[0].clone()], true)
+        },
+    )
+    .unwrap();
+    let original = commit(&mut replaced, params(vec![ids[0].clone()], true));
+    replaced.commits.remove(&original);
+    replaced
+        .commits
+        .insert(replacement.commit_id.clone(), replacement.commit);
+    replaced.head = replacement.commit_id.clone();
+    let repeat = create_commit(&replaced, params(vec![ids[0].clone()], false)).unwrap();
+    let error = append_commit(&mut replaced, repeat.commit_id, repeat.commit).unwrap_err();
+    assert_eq!(error.code(), "ENTRY_ALREADY_COMMITTED");
+    assert_eq!(error.context()["commitId"], json!(replacement.commit_id));
+
+    // A container edited by hand is caught by validation.
+    forged
+        .commits
+        .insert(created.commit_id.clone(), created.commit);
+    forged.head = created.commit_id.clone();
+    let result = validate_ledger(&forged, &ValidationOptions::default());
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].code, "ENTRY_ALREADY_COMMITTED");
+    assert_eq!(
+        result.errors[0].commit_id.as_deref(),
+        Some(created.commit_id.as_str())
+    );
+}
+
+#[test]
+fn refuses_to_merge_branches_that_each_committed_an_entry() {
+    let (base, ids) = ledger_with(&["user-1", "user-2", "user-3"]);
+    let mut ours = base.clone();
+    commit(
+        &mut ours,
+        params(vec![ids[0].clone(), ids[1].clone()], true),
+    );
+    let mut theirs = base;
+    commit(
+        &mut theirs,
+        params(vec![ids[2].clone(), ids[0].clone()], true),
+    );
+
+    let error = merge_ledgers(&ours, &theirs, None, None).unwrap_err();
+    assert_eq!(error.code(), "ENTRY_ALREADY_COMMITTED");
+    assert_eq!(error.context()["entryId"], json!(ids[0]));
+
+    // A non-strict merge commit appends, but validation reports the entry.
+    let mut merged = ours.clone();
+    merged.commits.extend(theirs.commits.clone());
+    let created = create_commit(
+        &merged,
+        CommitParams {
+            merge_parents: vec![theirs.head.clone()],
+            ..params(Vec::new(), false)
+        },
+    )
+    .unwrap();
+    append_commit_strict(&mut merged, created.commit_id, created.commit).unwrap();
+    let result = validate_ledger(&merged, &ValidationOptions::default());
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].code, "ENTRY_ALREADY_COMMITTED");
+    assert_eq!(
+        result.errors[0].entry_id.as_deref(),
+        Some(derive_entry_id(&entry("user-1")).unwrap().as_str())
+    );
+}
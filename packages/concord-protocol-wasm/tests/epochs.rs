@@ -0,0 +1,206 @@
+//! Ports `epochs.test.js`, run against the same fixture ledger.
+
+use concord_protocol_wasm::{
+    canonicalize_age_recipient, canonicalize_identity_key, derive_epoch_id, derive_signer_key_id,
+    get_active_epoch, get_epoch_chain, validate_ledger_epochs, Entry, EpochIdParams,
+    EpochValidationOptions, EpochValidationResult, LedgerContainer,
+};
+use serde_json::Value;
+
+const FIXTURE: &str =
+    include_str!("../../concord-protocol/src/__tests__/fixtures/epoch-ledger.valid.json");
+
+fn fixture_ledger() -> LedgerContainer {
+    serde_json::from_str(FIXTURE).unwrap()
+}
+
+fn validate(ledger: &LedgerContainer) -> EpochValidationResult {
+    validate_ledger_epochs(ledger, EpochValidationOptions::default()).unwrap()
+}
+
+fn has_code(result: &EpochValidationResult, code: &str) -> bool {
+    result.errors.iter().any(|error| error.code == code)
+}
+
+fn epoch_payload<'a>(ledger: &'a mut LedgerContainer, entry_id: &str) -> &'a mut Value {
+    ledger
+        .entries
+        .get_mut(entry_id)
+        .unwrap()
+        .payload
+        .as_mut()
+        .unwrap()
+}
+
+#[test]
+fn valid_epoch_chain_fixture() {
+    let ledger = fixture_ledger();
+    assert!(validate(&ledger).ok);
+    assert_eq!(get_epoch_chain(&ledger).unwrap().len(), 2);
+
+    let active = get_active_epoch(&ledger, EpochValidationOptions::default()).unwrap();
+    assert!(active.ok);
+    assert_eq!(
+        active.epoch.unwrap().payload.unwrap()["epochId"],
+        ledger.entries["entry-epoch-2"].payload.as_ref().unwrap()["epochId"]
+    );
+}
+
+#[test]
+fn derived_ids_match_fixture() {
+    let ledger = fixture_ledger();
+    let entry = &ledger.entries["entry-epoch-2"];
+    let payload = entry.payload.as_ref().unwrap();
+    let signer_key_id = derive_signer_key_id(&entry.author);
+    assert_eq!(signer_key_id, payload["signerKeyId"]);
+
+    let params = EpochIdParams {
+        signer_key_id,
+        encryption_public_key: payload["encryptionPublicKey"].as_str().unwrap().to_string(),
+        prev_epoch_id: payload["prevEpochId"].as_str().map(str::to_string),
+        created_at: payload["createdAt"].as_str().unwrap().to_string(),
+    };
+    assert_eq!(derive_epoch_id(&params).unwrap(), payload["epochId"]);
+}
+
+#[test]
+fn keys_are_trimmed_of_ecmascript_whitespace_only() {
+    // `\s` and `String.prototype.trim` match U+FEFF and U+3000 but not the
+    // U+0085 next-line control, which Unicode counts as whitespace.
+    let key = "\u{feff}age1 key\t\u{3000}\u{0085}\u{2028}";
+    assert_eq!(canonicalize_identity_key(key), "age1key\u{0085}");
+    assert_eq!(
+        canonicalize_age_recipient("\r\n\u{a0}\u{0085}age1 key\u{0085}\u{feff}\r"),
+        "\u{0085}age1 key\u{0085}"
+    );
+    assert_eq!(
+        derive_signer_key_id(key),
+        derive_signer_key_id("age1key\u{0085}")
+    );
+    assert_ne!(derive_signer_key_id(key), derive_signer_key_id("age1key"));
+}
+
+#[test]
+fn rejects_missing_genesis_epoch() {
+    let mut ledger = fixture_ledger();
+    ledger.commits.get_mut("genesis").unwrap().entries.clear();
+    let result = validate(&ledger);
+    assert!(!result.ok);
+    assert!(has_code(&result, "EPOCH_GENESIS_MISSING"));
+}
+
+#[test]
+fn rejects_prev_null_outside_genesis() {
+    let mut ledger = fixture_ledger();
+    epoch_payload(&mut ledger, "entry-epoch-2")["prevEpochId"] = Value::Null;
+    let result = validate(&ledger);
+    assert!(!result.ok);
+    assert!(has_code(&result, "EPOCH_PREV_NULL_OUTSIDE_GENESIS"));
+}
+
+#[test]
+fn rejects_multiple_genesis_epochs() {
+    let mut ledger = fixture_ledger();
+    let duplicate = ledger.entries["entry-epoch-1"].clone();
+    ledger
+        .entries
+        .insert("entry-epoch-1b".to_string(), duplicate);
+    ledger
+        .commits
+        .get_mut("genesis")
+        .unwrap()
+        .entries
+        .push("entry-epoch-1b".to_string());
+    let result = validate(&ledger);
+    assert!(!result.ok);
+    assert!(has_code(&result, "EPOCH_GENESIS_MULTIPLE"));
+}
+
+#[test]
+fn rejects_broken_chain() {
+    let mut ledger = fixture_ledger();
+    epoch_payload(&mut ledger, "entry-epoch-2")["prevEpochId"] = Value::from("not-the-prev-epoch");
+    let result = validate(&ledger);
+    assert!(!result.ok);
+    assert!(has_code(&result, "EPOCH_CHAIN_BROKEN"));
+}
+
+#[test]
+fn rejects_epoch_id_mismatch() {
+    let mut ledger = fixture_ledger();
+    epoch_payload(&mut ledger, "entry-epoch-2")["encryptionKeyId"] = Value::from("bad");
+    let result = validate(&ledger);
+    assert!(!result.ok);
+    assert!(has_code(&result, "EPOCH_ID_MISMATCH"));
+}
+
+#[test]
+fn rejects_signer_key_id_mismatch() {
+    let mut ledger = fixture_ledger();
+    epoch_payload(&mut ledger, "entry-epoch-1")["signerKeyId"] = Value::from("bad");
+    let result = validate(&ledger);
+    assert!(!result.ok);
+    assert!(has_code(&result, "SIGNER_KEY_ID_MISMATCH"));
+}
+
+#[test]
+fn rejects_entry_timestamp_after_commit() {
+    let mut ledger = fixture_ledger();
+    ledger.entries.get_mut("entry-epoch-2").unwrap().timestamp =
+        "2024-01-03T00:00:00.000Z".to_string();
+    let result = validate(&ledger);
+    assert!(!result.ok);
+    assert!(has_code(&result, "ENTRY_TIMESTAMP_AFTER_COMMIT"));
+}
+
+#[test]
+fn entry_timestamps_compare_as_rfc3339_instants() {
+    let after = |timestamp: &str| {
+        let mut ledger = fixture_ledger();
+        ledger.entries.get_mut("entry-epoch-2").unwrap().timestamp = timestamp.to_string();
+        has_code(&validate(&ledger), "ENTRY_TIMESTAMP_AFTER_COMMIT")
+    };
+    assert!(!after("2024-01-02T00:00:00Z"));
+    assert!(after("2024-01-02T00:00:00.001Z"));
+    assert!(after("2024-01-02t00:00:01z"));
+    assert!(after("2024-01-02 00:00:01+00:00"));
+    assert!(after("2024-01-01T23:00:01-01:00"));
+    assert!(!after("2024-01-02T01:00:00+01:00"));
+    // Forms Date.parse also reads, with offset-less ones in local time, and
+    // strings it rejects are never out of order.
+    assert!(!after("2024-01-03"));
+    assert!(!after("2024-01-03T00:00:00"));
+    assert!(!after("+002024-01-03T00:00:00Z"));
+    assert!(!after("2024-01-02T24:00:00Z"));
+    assert!(!after("Jan 3 2024"));
+}
+
+#[test]
+fn signature_callback_failures_are_reported_per_entry() {
+    let ledger = fixture_ledger();
+    fn verify(entry_id: &str, _: &Entry) -> Result<bool, String> {
+        match entry_id {
+            "entry-epoch-1" => Ok(true),
+            "entry-epoch-2" => Ok(false),
+            _ => Err("unexpected entry".to_string()),
+        }
+    }
+    let options = EpochValidationOptions {
+        verify_entry_signature: Some(&verify),
+    };
+    let result = validate_ledger_epochs(&ledger, options).unwrap();
+    assert!(!result.ok);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].code, "EPOCH_ENTRY_SIGNATURE_INVALID");
+    assert_eq!(result.errors[0].entry_id.as_deref(), Some("entry-epoch-2"));
+
+    fn failing(_: &str, _: &Entry) -> Result<bool, String> {
+        Err("verifier offline".to_string())
+    }
+    let options = EpochValidationOptions {
+        verify_entry_signature: Some(&failing),
+    };
+    let active = get_active_epoch(&ledger, options).unwrap();
+    assert!(!active.ok);
+    assert!(active.errors[0].message.contains("verifier offline"));
+}
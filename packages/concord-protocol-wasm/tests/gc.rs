@@ -0,0 +1,88 @@
+use concord_protocol_wasm::{
+    append_entry, derive_entry_id, gc_ledger, get_reachability_report, get_replay_entries,
+    validate_ledger, LedgerContainer, ValidationOptions,
+};
+use serde_json::json;
+
+mod common;
+
+use common::{commit_entries, entry, new_ledger};
+
+/// A ledger whose head was reset after a failed sync left a commit behind,
+/// plus an entry appended but never committed.
+fn ledger_with_garbage() -> (LedgerContainer, String) {
+    let mut ledger = new_ledger();
+    let genesis = ledger.head.clone();
+    let kept = commit_entries(&mut ledger, &["user-1"], "2026-01-01T00:01:00Z");
+    ledger.head = genesis;
+    let orphan = commit_entries(&mut ledger, &["user-1", "user-2"], "2026-01-01T00:01:00Z");
+    ledger.head = kept;
+    append_entry(&mut ledger, entry("pending")).unwrap();
+    (ledger, orphan)
+}
+
+#[test]
+fn reports_orphans_and_shared_entries() {
+    let (ledger, orphan) = ledger_with_garbage();
+    let report = get_reachability_report(&ledger).unwrap();
+    assert_eq!(report.orphan_commits, vec![orphan.clone()]);
+    assert_eq!(
+        report.unreferenced_entries,
+        vec![derive_entry_id(&entry("pending")).unwrap()]
+    );
+    let shared = &report.shared_entries[&derive_entry_id(&entry("user-1")).unwrap()];
+    assert_eq!(shared.len(), 2);
+    assert!(shared.contains(&orphan) && shared.contains(&ledger.head));
+    assert_eq!(report.shared_entries.len(), 1);
+
+    let mut clean = new_ledger();
+    commit_entries(&mut clean, &["user-1"], "2026-01-01T00:01:00Z");
+    commit_entries(&mut clean, &["user-2"], "2026-01-01T00:01:00Z");
+    let report = get_reachability_report(&clean).unwrap();
+    assert!(report.orphan_commits.is_empty() && report.unreferenced_entries.is_empty());
+    assert!(report.shared_entries.is_empty());
+}
+
+#[test]
+fn orphans_follow_the_head() {
+    let (mut ledger, orphan) = ledger_with_garbage();
+    let kept = std::mem::replace(&mut ledger.head, orphan);
+    let report = get_reachability_report(&ledger).unwrap();
+    assert_eq!(report.orphan_commits, vec![kept]);
+}
+
+#[test]
+fn gc_keeps_only_reachable_data() {
+    let (ledger, orphan) = ledger_with_garbage();
+    let collected = gc_ledger(&ledger, &[]).unwrap();
+    assert_eq!(collected.commits.len(), 2);
+    assert!(!collected.commits.contains_key(&orphan));
+    assert_eq!(collected.entries.len(), 1);
+    assert_eq!(
+        get_replay_entries(&collected).unwrap(),
+        get_replay_entries(&ledger).unwrap()
+    );
+    assert!(validate_ledger(&collected, &ValidationOptions::default()).ok);
+
+    let report = get_reachability_report(&collected).unwrap();
+    assert!(report.orphan_commits.is_empty());
+    assert!(report.unreferenced_entries.is_empty());
+    assert_eq!(gc_ledger(&collected, &[]).unwrap(), collected);
+}
+
+#[test]
+fn gc_keeps_retention_roots() {
+    let (ledger, orphan) = ledger_with_garbage();
+    let pending = derive_entry_id(&entry("pending")).unwrap();
+    let collected = gc_ledger(&ledger, &[orphan.clone(), pending.clone()]).unwrap();
+    assert_eq!(collected, ledger);
+
+    let collected = gc_ledger(&ledger, std::slice::from_ref(&pending)).unwrap();
+    assert!(!collected.commits.contains_key(&orphan));
+    assert_eq!(collected.entries.len(), 2);
+    assert!(collected.entries.contains_key(&pending));
+
+    let error = gc_ledger(&ledger, &["f".repeat(64)]).unwrap_err();
+    assert_eq!(error.code(), "UNKNOWN_ROOT");
+    assert_eq!(error.context()["root"], json!("f".repeat(64)));
+}
@@ -0,0 +1,130 @@
+//! Key-order conformance with `Array.prototype.sort`, which compares UTF-16
+//! code units. Expected orders were produced by `Object.keys(o).sort()` in
+//! Node.js.
+
+use concord_protocol_wasm::{canonical_stringify, hash_data};
+use serde_json::{Map, Value};
+
+fn key(code_points: &[u32]) -> String {
+    code_points
+        .iter()
+        .map(|cp| char::from_u32(*cp).unwrap())
+        .collect()
+}
+
+fn sorted_keys(keys: &[&[u32]]) -> Vec<String> {
+    let mut map = Map::new();
+    for (index, code_points) in keys.iter().enumerate() {
+        map.insert(key(code_points), Value::from(index));
+    }
+    let canonical = canonical_stringify(&Value::Object(map)).unwrap();
+    // Re-read the canonical output in document order.
+    let mut order = Vec::new();
+    let mut de = serde_json::Deserializer::from_str(&canonical);
+    let value: indexed::Ordered = serde::Deserialize::deserialize(&mut de).unwrap();
+    for (name, _) in value.0 {
+        order.push(name);
+    }
+    order
+}
+
+fn expected(keys: &[&[u32]]) -> Vec<String> {
+    keys.iter().map(|code_points| key(code_points)).collect()
+}
+
+mod indexed {
+    use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+    use serde_json::Value;
+    use std::fmt;
+
+    /// Object whose keys are kept in document order.
+    pub struct Ordered(pub Vec<(String, Value)>);
+
+    impl<'de> Deserialize<'de> for Ordered {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct OrderedVisitor;
+            impl<'de> Visitor<'de> for OrderedVisitor {
+                type Value = Ordered;
+                fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.write_str("an object")
+                }
+                fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Ordered, A::Error> {
+                    let mut items = Vec::new();
+                    while let Some(item) = map.next_entry()? {
+                        items.push(item);
+                    }
+                    Ok(Ordered(items))
+                }
+            }
+            deserializer.deserialize_map(OrderedVisitor)
+        }
+    }
+}
+
+#[test]
+fn astral_keys_sort_before_high_bmp_keys() {
+    assert_eq!(
+        sorted_keys(&[&[0xffff], &[0x1f600]]),
+        expected(&[&[0x1f600], &[0xffff]])
+    );
+    assert_eq!(
+        sorted_keys(&[&[0xff5e], &[0x10000], &[0xe000], &[0x61]]),
+        expected(&[&[0x61], &[0x10000], &[0xe000], &[0xff5e]])
+    );
+}
+
+#[test]
+fn surrogate_pairs_compare_by_code_unit() {
+    assert_eq!(
+        sorted_keys(&[&[0x20000], &[0x1f600], &[0x10ffff], &[0xfffe]]),
+        expected(&[&[0x1f600], &[0x20000], &[0x10ffff], &[0xfffe]])
+    );
+    assert_eq!(
+        sorted_keys(&[&[0x61, 0x1f600], &[0x61, 0xfffd], &[0x61], &[0x61, 0x62]]),
+        expected(&[&[0x61], &[0x61, 0x62], &[0x61, 0x1f600], &[0x61, 0xfffd]])
+    );
+}
+
+#[test]
+fn mixed_bmp_and_astral_keys() {
+    assert_eq!(
+        sorted_keys(&[
+            &[0x1f642, 0x78],
+            &[0x1f642],
+            &[0xfb01],
+            &[0xd7ff],
+            &[0x7a],
+            &[0x5a],
+            &[0xe9],
+            &[0x0],
+        ]),
+        expected(&[
+            &[0x0],
+            &[0x5a],
+            &[0x7a],
+            &[0xe9],
+            &[0xd7ff],
+            &[0x1f642],
+            &[0x1f642, 0x78],
+            &[0xfb01],
+        ])
+    );
+}
+
+#[test]
+fn canonical_output_and_hash_match_typescript() {
+    let value: Value = serde_json::from_str("{\"\u{ffff}\":1,\"\u{1f600}\":1}").unwrap();
+    let canonical = canonical_stringify(&value).unwrap();
+    assert_eq!(canonical, "{\"\u{1f600}\":1,\"\u{ffff}\":1}");
+
+    let nested: Value =
+        serde_json::from_str("{\"outer\":{\"\u{e000}\":true,\"\u{10000}\":false}}").unwrap();
+    assert_eq!(
+        canonical_stringify(&nested).unwrap(),
+        "{\"outer\":{\"\u{10000}\":false,\"\u{e000}\":true}}"
+    );
+    assert_eq!(
+        hash_data(&value).unwrap(),
+        "219abb8878090969505c14ee00ad5962d1f2a6e9f8c2dd29ee5bdb169706e84e"
+    );
+}
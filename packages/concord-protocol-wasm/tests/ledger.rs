@@ -0,0 +1,187 @@
+use concord_protocol_wasm::{
+    append_commit, append_commit_strict, append_entry, create_commit, derive_commit_id,
+    get_commit_chain, get_commit_signing_bytes, get_commit_signing_payload, get_replay_entries,
+    get_replay_entries_since, validate_ledger, Commit, CommitParams, ValidationOptions,
+};
+use serde_json::json;
+
+mod common;
+
+use common::{commit_entries, entry, new_ledger};
+
+#[test]
+fn native_append_commit_and_replay() {
+    let mut ledger = new_ledger();
+    let genesis_id = ledger.head.clone();
+
+    let first = append_entry(&mut ledger, entry("user-1")).unwrap();
+    let second = append_entry(&mut ledger, entry("user-2")).unwrap();
+    let created = create_commit(
+        &ledger,
+        CommitParams {
+            entries: vec![first, second],
+            timestamp: Some("2026-01-01T00:01:00Z".to_string()),
+            ..CommitParams::default()
+        },
+    )
+    .unwrap();
+    append_commit_strict(&mut ledger, created.commit_id.clone(), created.commit).unwrap();
+
+    assert_eq!(
+        get_commit_chain(&ledger).unwrap(),
+        vec![genesis_id, created.commit_id]
+    );
+    let replayed = get_replay_entries(&ledger).unwrap();
+    assert_eq!(replayed, vec![entry("user-1"), entry("user-2")]);
+    assert!(validate_ledger(&ledger, &ValidationOptions::default()).ok);
+}
+
+#[test]
+fn native_errors_carry_codes() {
+    let mut ledger = new_ledger();
+    append_entry(&mut ledger, entry("user-1")).unwrap();
+    let error = append_entry(&mut ledger, entry("user-1")).unwrap_err();
+    assert_eq!(error.code(), "DUPLICATE_ENTRY");
+
+    let created = create_commit(&ledger, CommitParams::default()).unwrap();
+    let before = ledger.clone();
+    let error = append_commit_strict(
+        &mut ledger,
+        "not-the-id".to_string(),
+        created.commit.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error.code(), "COMMIT_ID_MISMATCH");
+    assert_eq!(ledger, before);
+
+    append_commit(
+        &mut ledger,
+        created.commit_id.clone(),
+        created.commit.clone(),
+    )
+    .unwrap();
+    let error = append_commit(&mut ledger, created.commit_id, created.commit).unwrap_err();
+    assert_eq!(error.code(), "DUPLICATE_COMMIT");
+}
+
+#[test]
+fn commit_seal_is_excluded_from_commit_id_and_preserved() {
+    let mut ledger = new_ledger();
+    let genesis_id = ledger.head.clone();
+    let entry_id = append_entry(&mut ledger, entry("user-1")).unwrap();
+    let created = create_commit(
+        &ledger,
+        CommitParams {
+            entries: vec![entry_id.clone()],
+            timestamp: Some("2026-01-01T00:01:00Z".to_string()),
+            ..CommitParams::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        get_commit_signing_payload(&created.commit).unwrap(),
+        format!(
+            r#"{{"entries":["{}"],"metadata":null,"parent":"{}","timestamp":"2026-01-01T00:01:00Z"}}"#,
+            entry_id, ledger.head
+        )
+    );
+
+    let sealed = Commit {
+        signature: Some("c2lnbmF0dXJl".to_string()),
+        ..created.commit.clone()
+    };
+    assert_eq!(
+        get_commit_signing_bytes(&sealed).unwrap(),
+        get_commit_signing_bytes(&created.commit).unwrap()
+    );
+    assert_eq!(derive_commit_id(&sealed).unwrap(), created.commit_id);
+
+    append_commit_strict(&mut ledger, created.commit_id.clone(), sealed).unwrap();
+    assert_eq!(
+        ledger.commits[&created.commit_id].signature.as_deref(),
+        Some("c2lnbmF0dXJl")
+    );
+    let json = serde_json::to_value(&ledger).unwrap();
+    assert_eq!(
+        json["commits"][&created.commit_id]["signature"],
+        "c2lnbmF0dXJl"
+    );
+    assert!(json["commits"][&genesis_id].get("signature").is_none());
+}
+
+#[test]
+fn validate_ledger_rederives_content_addresses() {
+    let mut ledger = new_ledger();
+    let entry_id = append_entry(&mut ledger, entry("user-1")).unwrap();
+    let created = create_commit(
+        &ledger,
+        CommitParams {
+            entries: vec![entry_id.clone()],
+            timestamp: Some("2026-01-01T00:01:00Z".to_string()),
+            ..CommitParams::default()
+        },
+    )
+    .unwrap();
+    append_commit_strict(&mut ledger, created.commit_id.clone(), created.commit).unwrap();
+
+    let mut tampered = ledger.clone();
+    tampered.entries.get_mut(&entry_id).unwrap().payload = Some(json!({ "id": "user-2" }));
+    tampered
+        .commits
+        .get_mut(&created.commit_id)
+        .unwrap()
+        .timestamp = "2026-01-01T00:02:00Z".to_string();
+
+    let result = validate_ledger(&tampered, &ValidationOptions::default());
+    assert!(!result.ok);
+    assert_eq!(result.errors.len(), 2);
+    assert_eq!(result.errors[0].code, "COMMIT_ID_MISMATCH");
+    assert_eq!(
+        result.errors[0].commit_id.as_deref(),
+        Some(created.commit_id.as_str())
+    );
+    assert_eq!(result.errors[1].code, "ENTRY_ID_MISMATCH");
+    assert_eq!(
+        result.errors[1].entry_id.as_deref(),
+        Some(entry_id.as_str())
+    );
+}
+
+#[test]
+fn replay_since_cursor_returns_only_newer_entries() {
+    let mut ledger = new_ledger();
+    let genesis_id = ledger.head.clone();
+    let first = commit_entries(&mut ledger, &["user-1", "user-2"], "2026-01-01T00:01:00Z");
+
+    let since_genesis = get_replay_entries_since(&ledger, &genesis_id).unwrap();
+    assert_eq!(since_genesis.entries, get_replay_entries(&ledger).unwrap());
+    assert_eq!(since_genesis.cursor, first);
+
+    let second = commit_entries(&mut ledger, &["user-3"], "2026-01-01T00:02:00Z");
+    let since_first = get_replay_entries_since(&ledger, &since_genesis.cursor).unwrap();
+    assert_eq!(since_first.entries, vec![entry("user-3")]);
+    assert_eq!(since_first.cursor, second);
+
+    let at_head = get_replay_entries_since(&ledger, &second).unwrap();
+    assert!(at_head.entries.is_empty());
+    assert_eq!(at_head.cursor, second);
+}
+
+#[test]
+fn replay_since_rejects_cursors_off_the_chain() {
+    let mut ledger = new_ledger();
+    let genesis_id = ledger.head.clone();
+    let mut fork = ledger.clone();
+    commit_entries(&mut ledger, &["user-1"], "2026-01-01T00:01:00Z");
+    let forked = commit_entries(&mut fork, &["user-2"], "2026-01-01T00:01:00Z");
+    ledger
+        .commits
+        .insert(forked.clone(), fork.commits[&forked].clone());
+
+    let error = get_replay_entries_since(&ledger, &forked).unwrap_err();
+    assert_eq!(error.code(), "CURSOR_NOT_ON_CHAIN");
+    assert_eq!(error.context().get("commitId"), Some(&json!(forked)));
+    let error = get_replay_entries_since(&ledger, "unknown").unwrap_err();
+    assert_eq!(error.code(), "CURSOR_NOT_ON_CHAIN");
+    assert!(get_replay_entries_since(&ledger, &genesis_id).is_ok());
+}
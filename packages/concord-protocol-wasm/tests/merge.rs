@@ -0,0 +1,179 @@
+use concord_protocol_wasm::{
+    append_commit, create_commit, create_ledger, derive_commit_id, get_commit_chain,
+    get_commit_signing_payload, get_merge_base, get_replay_entries, get_replay_entries_since,
+    is_ancestor, merge_ledgers, validate_ledger, Commit, CommitParams, LedgerContainer,
+    ValidationOptions,
+};
+use serde_json::json;
+
+mod common;
+
+use common::{commit_entry, entry, new_ledger};
+
+/// A shared base commit, then one offline commit on each of two devices.
+fn diverged() -> (LedgerContainer, LedgerContainer, String) {
+    let mut base = new_ledger();
+    let shared = commit_entry(&mut base, "user-1", "2026-01-01T00:01:00Z");
+    let mut laptop = base.clone();
+    let mut phone = base;
+    commit_entry(&mut laptop, "user-2", "2026-01-01T00:02:00Z");
+    commit_entry(&mut phone, "user-3", "2026-01-01T00:03:00Z");
+    (laptop, phone, shared)
+}
+
+#[test]
+fn merge_joins_divergent_heads() {
+    let (laptop, phone, shared) = diverged();
+    let merged = merge_ledgers(
+        &laptop,
+        &phone,
+        Some(json!({ "message": "sync" })),
+        Some("2026-01-01T00:04:00Z".to_string()),
+    )
+    .unwrap();
+
+    let merge = &merged.commits[&merged.head];
+    assert_eq!(merge.parent.as_deref(), Some(laptop.head.as_str()));
+    assert_eq!(merge.merge_parents, vec![phone.head.clone()]);
+    assert!(merge.entries.is_empty());
+
+    assert_eq!(
+        get_merge_base(&merged, &laptop.head, &phone.head).unwrap(),
+        Some(shared.clone())
+    );
+    assert!(is_ancestor(&merged, &phone.head, &merged.head).unwrap());
+    assert!(!is_ancestor(&merged, &phone.head, &laptop.head).unwrap());
+
+    let chain = get_commit_chain(&merged).unwrap();
+    assert_eq!(chain.len(), 5);
+    assert_eq!(
+        &chain[1..],
+        &[
+            shared,
+            laptop.head.clone(),
+            phone.head.clone(),
+            merged.head.clone()
+        ]
+    );
+    assert_eq!(
+        get_replay_entries(&merged).unwrap(),
+        vec![entry("user-1"), entry("user-2"), entry("user-3")]
+    );
+    assert!(validate_ledger(&merged, &ValidationOptions::default()).ok);
+
+    // Both devices replay to the same state whichever side merges.
+    let reverse = merge_ledgers(
+        &phone,
+        &laptop,
+        None,
+        Some("2026-01-01T00:04:00Z".to_string()),
+    )
+    .unwrap();
+    assert_eq!(
+        get_replay_entries(&reverse).unwrap(),
+        vec![entry("user-1"), entry("user-3"), entry("user-2")]
+    );
+
+    // A cursor on the merged-in branch picks up the other branch.
+    let since = get_replay_entries_since(&merged, &phone.head).unwrap();
+    assert_eq!(since.entries, vec![entry("user-2")]);
+    assert_eq!(since.cursor, merged.head);
+}
+
+#[test]
+fn merge_fast_forwards_when_one_side_is_behind() {
+    let mut behind = new_ledger();
+    commit_entry(&mut behind, "user-1", "2026-01-01T00:01:00Z");
+    let mut ahead = behind.clone();
+    commit_entry(&mut ahead, "user-2", "2026-01-01T00:02:00Z");
+
+    let forwarded = merge_ledgers(&behind, &ahead, None, None).unwrap();
+    assert_eq!(forwarded, ahead);
+    let unchanged = merge_ledgers(&ahead, &behind, None, None).unwrap();
+    assert_eq!(unchanged, ahead);
+
+    let other = create_ledger(None, Some("2026-02-01T00:00:00Z".to_string())).unwrap();
+    let error = merge_ledgers(&ahead, &other, None, None).unwrap_err();
+    assert_eq!(error.code(), "UNRELATED_HISTORIES");
+}
+
+#[test]
+fn merge_parents_are_part_of_the_commit_id() {
+    let (laptop, phone, _) = diverged();
+    let merged = merge_ledgers(
+        &laptop,
+        &phone,
+        None,
+        Some("2026-01-01T00:04:00Z".to_string()),
+    )
+    .unwrap();
+    let merge = merged.commits[&merged.head].clone();
+    assert_eq!(derive_commit_id(&merge).unwrap(), merged.head);
+    assert_eq!(
+        get_commit_signing_payload(&merge).unwrap(),
+        format!(
+            r#"{{"entries":[],"mergeParents":["{}"],"metadata":null,"parent":"{}","timestamp":"2026-01-01T00:04:00Z"}}"#,
+            phone.head, laptop.head
+        )
+    );
+    let linear = Commit {
+        merge_parents: Vec::new(),
+        ..merge.clone()
+    };
+    assert!(!get_commit_signing_payload(&linear)
+        .unwrap()
+        .contains("mergeParents"));
+    assert_ne!(derive_commit_id(&linear).unwrap(), merged.head);
+
+    let serialized = serde_json::to_value(&laptop).unwrap();
+    assert!(serialized["commits"][&laptop.head]
+        .get("mergeParents")
+        .is_none());
+}
+
+#[test]
+fn validation_follows_every_parent() {
+    let (laptop, phone, _) = diverged();
+    let mut merged = merge_ledgers(&laptop, &phone, None, None).unwrap();
+
+    let mut missing = merged.clone();
+    missing.commits.remove(&phone.head);
+    let result = validate_ledger(&missing, &ValidationOptions::default());
+    assert!(result
+        .errors
+        .iter()
+        .any(|error| error.code == "MISSING_COMMIT"));
+
+    let created = create_commit(
+        &merged,
+        CommitParams {
+            merge_parents: vec![phone.head.clone(), phone.head.clone()],
+            ..CommitParams::default()
+        },
+    )
+    .unwrap();
+    let error = append_commit(&mut merged, created.commit_id, created.commit).unwrap_err();
+    assert_eq!(error.code(), "INVALID_COMMIT");
+    assert_eq!(error.context()["errors"][0]["path"], "mergeParents[1]");
+
+    let mut second_root = merged.clone();
+    let stray = create_ledger(None, Some("2026-02-01T00:00:00Z".to_string())).unwrap();
+    second_root
+        .commits
+        .insert(stray.head.clone(), stray.commits[&stray.head].clone());
+    let created = create_commit(
+        &second_root,
+        CommitParams {
+            merge_parents: vec![stray.head.clone()],
+            ..CommitParams::default()
+        },
+    )
+    .unwrap();
+    append_commit(&mut second_root, created.commit_id.clone(), created.commit).unwrap();
+    let result = validate_ledger(&second_root, &ValidationOptions::default());
+    assert!(result
+        .errors
+        .iter()
+        .any(|error| error.code == "GENESIS_INVALID"
+            && error.commit_id.as_deref() == Some(stray.head.as_str())));
+}
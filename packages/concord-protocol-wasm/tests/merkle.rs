@@ -0,0 +1,123 @@
+use concord_protocol_wasm::{
+    append_commit, append_commit_strict, append_entry, compute_entries_root, create_commit,
+    create_ledger, derive_commit_id, prove_entry_inclusion, validate_ledger,
+    verify_entry_inclusion, CommitParams, Entry, LedgerContainer, ValidationOptions,
+};
+use serde_json::json;
+
+fn entry(id: usize) -> Entry {
+    Entry {
+        kind: "concord/user/added".to_string(),
+        timestamp: "2026-01-01T00:00:00Z".to_string(),
+        author: "author-1".to_string(),
+        payload: Some(json!({ "id": id })),
+        signature: None,
+    }
+}
+
+fn ledger_with_root(count: usize) -> (LedgerContainer, Vec<Entry>, String) {
+    let mut ledger = create_ledger(None, Some("2026-01-01T00:00:00Z".to_string())).unwrap();
+    let entries: Vec<Entry> = (0..count).map(entry).collect();
+    let entry_ids = entries
+        .iter()
+        .map(|entry| append_entry(&mut ledger, entry.clone()).unwrap())
+        .collect();
+    let created = create_commit(
+        &ledger,
+        CommitParams {
+            entries: entry_ids,
+            timestamp: Some("2026-01-01T00:01:00Z".to_string()),
+            entries_root: true,
+            ..CommitParams::default()
+        },
+    )
+    .unwrap();
+    append_commit_strict(&mut ledger, created.commit_id.clone(), created.commit).unwrap();
+    (ledger, entries, created.commit_id)
+}
+
+#[test]
+fn every_entry_has_a_verifying_proof() {
+    for count in 1..=9 {
+        let (ledger, entries, commit_id) = ledger_with_root(count);
+        let commit = &ledger.commits[&commit_id];
+        assert_eq!(
+            commit.entries_root.as_deref(),
+            Some(compute_entries_root(&commit.entries).as_str())
+        );
+        for (index, entry_id) in commit.entries.iter().enumerate() {
+            let proof = prove_entry_inclusion(&ledger, &commit_id, entry_id).unwrap();
+            assert_eq!(proof.index, index);
+            assert!(proof.commit.entries.is_empty());
+            assert!(proof.path.len() <= 4);
+            assert!(
+                verify_entry_inclusion(&entries[index], &proof),
+                "{}/{}",
+                index,
+                count
+            );
+            if count > 1 {
+                let other = &entries[(index + 1) % count];
+                assert!(!verify_entry_inclusion(other, &proof));
+            }
+        }
+    }
+}
+
+#[test]
+fn tampered_proofs_do_not_verify() {
+    let (ledger, entries, commit_id) = ledger_with_root(5);
+    let entry_id = ledger.commits[&commit_id].entries[2].clone();
+    let proof = prove_entry_inclusion(&ledger, &commit_id, &entry_id).unwrap();
+    assert!(verify_entry_inclusion(&entries[2], &proof));
+
+    let mut moved = proof.clone();
+    moved.index = 3;
+    assert!(!verify_entry_inclusion(&entries[2], &moved));
+
+    let mut bad_path = proof.clone();
+    bad_path.path[0] = "00".repeat(32);
+    assert!(!verify_entry_inclusion(&entries[2], &bad_path));
+
+    // Swapping the root breaks the CommitID binding.
+    let mut swapped = proof.clone();
+    swapped.commit.entries_root = Some(compute_entries_root(std::slice::from_ref(&entry_id)));
+    swapped.path.clear();
+    swapped.index = 0;
+    swapped.leaf_count = 1;
+    assert!(!verify_entry_inclusion(&entries[2], &swapped));
+    swapped.commit_id = derive_commit_id(&swapped.commit).unwrap();
+    assert!(verify_entry_inclusion(&entries[2], &swapped));
+    assert_ne!(swapped.commit_id, commit_id);
+}
+
+#[test]
+fn root_must_match_entries() {
+    let (mut ledger, _, commit_id) = ledger_with_root(3);
+    assert!(validate_ledger(&ledger, &ValidationOptions::default()).ok);
+
+    let error = prove_entry_inclusion(&ledger, &ledger.head.clone(), &"f".repeat(64)).unwrap_err();
+    assert_eq!(error.code(), "ENTRY_NOT_IN_COMMIT");
+    let genesis = ledger.commits[&commit_id].parent.clone().unwrap();
+    let error = prove_entry_inclusion(&ledger, &genesis, &"f".repeat(64)).unwrap_err();
+    assert_eq!(error.code(), "MISSING_ENTRIES_ROOT");
+
+    // The CommitID covers the root, not the list, so a reordered list keeps
+    // the ID and must be caught by validation.
+    let mut commit = ledger.commits[&commit_id].clone();
+    commit.entries.reverse();
+    assert_eq!(derive_commit_id(&commit).unwrap(), commit_id);
+    ledger.commits.insert(commit_id.clone(), commit.clone());
+    let result = validate_ledger(&ledger, &ValidationOptions::default());
+    assert!(result
+        .errors
+        .iter()
+        .any(|error| error.code == "ENTRIES_ROOT_MISMATCH"
+            && error.commit_id.as_deref() == Some(commit_id.as_str())));
+
+    let mut ledger = ledger.clone();
+    ledger.commits.remove(&commit_id);
+    ledger.head = genesis;
+    let error = append_commit(&mut ledger, commit_id, commit).unwrap_err();
+    assert_eq!(error.code(), "INVALID_COMMIT");
+}
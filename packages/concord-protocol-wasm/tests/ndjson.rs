@@ -0,0 +1,149 @@
+use concord_protocol_wasm::{
+    append_entry, derive_commit_id, export_ledger_ndjson, get_commit_chain, ledger_ndjson_lines,
+    Commit, CommitParams, Entry, LedgerContainer, NdjsonReader,
+};
+use serde_json::{json, Value};
+
+mod common;
+
+use common::{commit, entry, new_ledger};
+
+/// An entry with a fractional payload number, which must survive the
+/// round trip.
+fn scored_entry(id: &str) -> Entry {
+    Entry {
+        payload: Some(json!({ "id": id, "score": 1.5 })),
+        ..entry(id)
+    }
+}
+
+fn sample_ledger() -> LedgerContainer {
+    let mut ledger = new_ledger();
+    for (index, ids) in [vec!["user-1", "user-2"], vec!["user-3"]]
+        .iter()
+        .enumerate()
+    {
+        let entries = ids
+            .iter()
+            .map(|id| append_entry(&mut ledger, scored_entry(id)).unwrap())
+            .collect();
+        commit(
+            &mut ledger,
+            CommitParams {
+                entries,
+                timestamp: Some(format!("2026-01-01T00:0{}:00Z", index + 1)),
+                ..CommitParams::default()
+            },
+        );
+    }
+    append_entry(&mut ledger, scored_entry("uncommitted")).unwrap();
+    ledger
+}
+
+/// The ledger as it reads back from JSON, where `null` metadata is `None`.
+fn from_json(ledger: &LedgerContainer) -> LedgerContainer {
+    serde_json::from_value(serde_json::to_value(ledger).unwrap()).unwrap()
+}
+
+fn read(text: &str) -> concord_protocol_wasm::Result<LedgerContainer> {
+    let mut reader = NdjsonReader::new();
+    reader.push(text)?;
+    reader.finish()
+}
+
+#[test]
+fn export_writes_header_then_replay_order() {
+    let ledger = sample_ledger();
+    let records: Vec<Value> = ledger_ndjson_lines(&ledger)
+        .unwrap()
+        .map(|line| serde_json::from_str(&line).unwrap())
+        .collect();
+    let types: Vec<&str> = records
+        .iter()
+        .map(|record| record["type"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        types,
+        vec!["header", "commit", "entry", "entry", "commit", "entry", "commit", "entry"]
+    );
+    assert_eq!(records[0]["head"], json!(ledger.head));
+    let commits: Vec<&str> = records
+        .iter()
+        .filter(|record| record["type"] == "commit")
+        .map(|record| record["commitId"].as_str().unwrap())
+        .collect();
+    assert_eq!(commits, get_commit_chain(&ledger).unwrap());
+}
+
+#[test]
+fn reader_round_trips_across_arbitrary_chunks() {
+    let ledger = from_json(&sample_ledger());
+    let text = export_ledger_ndjson(&ledger).unwrap();
+    assert_eq!(read(&text).unwrap(), ledger);
+
+    let mut reader = NdjsonReader::new();
+    for chunk in text.as_bytes().chunks(7) {
+        reader.push(std::str::from_utf8(chunk).unwrap()).unwrap();
+    }
+    assert_eq!(reader.lines_read(), 8);
+    assert_eq!(reader.finish().unwrap(), ledger);
+
+    let unterminated = text.replace('\n', "\r\n");
+    assert_eq!(read(unterminated.trim_end()).unwrap(), ledger);
+}
+
+#[test]
+fn reader_rejects_records_as_they_arrive() {
+    let ledger = sample_ledger();
+    let lines: Vec<String> = ledger_ndjson_lines(&ledger).unwrap().collect();
+
+    let mut reader = NdjsonReader::new();
+    let error = reader.push(&format!("{}\n", lines[1])).unwrap_err();
+    assert_eq!(error.code(), "INVALID_NDJSON");
+    assert_eq!(error.context()["line"], json!(1));
+
+    // A commit before its entries fails on that line, not at the end.
+    let mut reader = NdjsonReader::new();
+    reader
+        .push(&format!("{}\n{}\n", lines[0], lines[1]))
+        .unwrap();
+    let error = reader.push(&format!("{}\n", lines[4])).unwrap_err();
+    assert_eq!(error.code(), "MISSING_ENTRY");
+    assert_eq!(error.context()["line"], json!(3));
+    assert!(error.context()["entryId"].is_string());
+
+    let tampered = lines[2].replace("user-1", "user-9");
+    let error = read(&format!("{}\n\n{}\n{}", lines[0], lines[1], tampered)).unwrap_err();
+    assert_eq!(error.code(), "ENTRY_ID_MISMATCH");
+    assert_eq!(error.context()["line"], json!(4));
+
+    let error = read(&lines[..lines.len() - 3].join("\n")).unwrap_err();
+    assert_eq!(error.code(), "INVALID_NDJSON");
+
+    let error = read(&format!("{}\nnot json", lines.join("\n"))).unwrap_err();
+    assert_eq!(error.code(), "INVALID_NDJSON");
+    assert_eq!(error.context()["line"], json!(9));
+}
+
+#[test]
+fn reader_checks_the_genesis_record() {
+    let lines: Vec<String> = ledger_ndjson_lines(&sample_ledger()).unwrap().collect();
+    let mut genesis: Value = serde_json::from_str(&lines[1]).unwrap();
+    genesis["commit"]["metadata"] = json!({ "spec": "concord-protocol@1.0" });
+    genesis["commitId"] = json!(derive_commit_id(
+        &serde_json::from_value::<Commit>(genesis["commit"].clone()).unwrap()
+    )
+    .unwrap());
+
+    let error = read(&format!("{}\n{}", lines[0], genesis)).unwrap_err();
+    assert_eq!(error.code(), "INVALID_COMMIT");
+    assert_eq!(error.context()["line"], json!(2));
+    assert_eq!(
+        error.context()["errors"][0]["code"],
+        json!("GENESIS_INVALID")
+    );
+    assert_eq!(
+        error.context()["errors"][0]["path"],
+        json!("metadata.genesis")
+    );
+}
@@ -0,0 +1,135 @@
+use concord_protocol_wasm::{
+    append_entry, derive_entry_id, merge_ledgers, prove_entry_in_ledger, verify_entry_in_ledger,
+    CommitParams, LedgerContainer,
+};
+
+mod common;
+
+use common::{commit, entry, new_ledger};
+
+fn commit_entries(ledger: &mut LedgerContainer, ids: &[&str], timestamp: &str, root: bool) {
+    let entries = ids
+        .iter()
+        .map(|id| append_entry(ledger, entry(id)).unwrap())
+        .collect();
+    commit(
+        ledger,
+        CommitParams {
+            entries,
+            timestamp: Some(timestamp.to_string()),
+            entries_root: root,
+            ..CommitParams::default()
+        },
+    );
+}
+
+fn linear_ledger(root: bool) -> LedgerContainer {
+    let mut ledger = new_ledger();
+    for index in 1..=4 {
+        let ids = [format!("user-{}a", index), format!("user-{}b", index)];
+        let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+        commit_entries(
+            &mut ledger,
+            &ids,
+            &format!("2026-01-01T00:0{}:00Z", index),
+            root,
+        );
+    }
+    ledger
+}
+
+#[test]
+fn proves_an_entry_back_from_head() {
+    let ledger = linear_ledger(false);
+    let proof =
+        prove_entry_in_ledger(&ledger, &derive_entry_id(&entry("user-2a")).unwrap()).unwrap();
+    assert_eq!(proof.commits.len(), 3);
+    assert_eq!(proof.commits[0].commit_id, ledger.head);
+    assert!(verify_entry_in_ledger(
+        &entry("user-2a"),
+        &proof,
+        &ledger.head
+    ));
+
+    assert!(!verify_entry_in_ledger(
+        &entry("user-2b"),
+        &proof,
+        &ledger.head
+    ));
+    let genesis = proof.commits[2].commit.parent.clone().unwrap();
+    assert!(!verify_entry_in_ledger(&entry("user-2a"), &proof, &genesis));
+
+    let mut gapped = proof.clone();
+    gapped.commits.remove(1);
+    assert!(!verify_entry_in_ledger(
+        &entry("user-2a"),
+        &gapped,
+        &ledger.head
+    ));
+
+    let mut edited = proof.clone();
+    edited.commits[1].commit.timestamp = "2026-01-02T00:00:00Z".to_string();
+    assert!(!verify_entry_in_ledger(
+        &entry("user-2a"),
+        &edited,
+        &ledger.head
+    ));
+
+    let error = prove_entry_in_ledger(&ledger, &"f".repeat(64)).unwrap_err();
+    assert_eq!(error.code(), "MISSING_ENTRY");
+}
+
+#[test]
+fn commits_with_roots_travel_without_entries() {
+    let ledger = linear_ledger(true);
+    let entry_id = derive_entry_id(&entry("user-1b")).unwrap();
+    let proof = prove_entry_in_ledger(&ledger, &entry_id).unwrap();
+    assert_eq!(proof.commits.len(), 4);
+    for created in &proof.commits[..3] {
+        assert!(created.commit.entries.is_empty());
+    }
+    assert_eq!(proof.commits[3].commit.entries.len(), 2);
+    assert!(verify_entry_in_ledger(
+        &entry("user-1b"),
+        &proof,
+        &ledger.head
+    ));
+
+    // The last commit's list must still match its root.
+    let mut trimmed = proof.clone();
+    trimmed.commits[3]
+        .commit
+        .entries
+        .retain(|id| *id == entry_id);
+    assert!(!verify_entry_in_ledger(
+        &entry("user-1b"),
+        &trimmed,
+        &ledger.head
+    ));
+}
+
+#[test]
+fn follows_merge_parents_to_a_merged_branch() {
+    let base = linear_ledger(false);
+    let mut branch = base.clone();
+    commit_entries(&mut branch, &["side"], "2026-01-01T00:05:00Z", false);
+    let mut main = base;
+    commit_entries(&mut main, &["main"], "2026-01-01T00:06:00Z", false);
+    let merged = merge_ledgers(
+        &main,
+        &branch,
+        None,
+        Some("2026-01-01T00:07:00Z".to_string()),
+    )
+    .unwrap();
+
+    let entry_id = derive_entry_id(&entry("side")).unwrap();
+    let proof = prove_entry_in_ledger(&merged, &entry_id).unwrap();
+    let path: Vec<&str> = proof
+        .commits
+        .iter()
+        .map(|created| created.commit_id.as_str())
+        .collect();
+    assert_eq!(path, vec![merged.head.as_str(), branch.head.as_str()]);
+    assert!(verify_entry_in_ledger(&entry("side"), &proof, &merged.head));
+}
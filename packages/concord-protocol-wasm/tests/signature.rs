@@ -0,0 +1,113 @@
+//! Ed25519 entry signatures in the `@ternent/identity` format. The signed
+//! fixture was produced with Node's `crypto.sign` over
+//! `"ternent-seal/v2" + 0x00 + signing bytes`.
+
+use concord_protocol_wasm::{
+    append_commit_strict, append_entry, create_commit, create_ledger, validate_ledger,
+    verify_entry_signature, CommitParams, Entry, ValidationOptions,
+};
+use serde_json::json;
+
+const AUTHOR: &str = "6kpsY-KcUgq-9VB7Ey7F-ZVHdq6-vnuSQh7qaRRG0iw";
+const SIGNATURE: &str =
+    "5efzaHeJctCDD-EalmZVlJza8M4h2gcaTDO5rcxBC1221OkW2Zdz0FgclEd-qNqqmatZaLIqV5_1BvJL7PP8Aw";
+
+fn signed_entry() -> Entry {
+    Entry {
+        kind: "concord/user/added".to_string(),
+        timestamp: "2026-01-01T00:00:00Z".to_string(),
+        author: AUTHOR.to_string(),
+        payload: Some(json!({ "id": "user-1" })),
+        signature: Some(SIGNATURE.to_string()),
+    }
+}
+
+fn ledger_with(entry: Entry) -> concord_protocol_wasm::LedgerContainer {
+    let mut ledger = create_ledger(None, Some("2026-01-01T00:00:00Z".to_string())).unwrap();
+    let entry_id = append_entry(&mut ledger, entry).unwrap();
+    let created = create_commit(
+        &ledger,
+        CommitParams {
+            entries: vec![entry_id],
+            ..CommitParams::default()
+        },
+    )
+    .unwrap();
+    append_commit_strict(&mut ledger, created.commit_id, created.commit).unwrap();
+    ledger
+}
+
+fn verifying() -> ValidationOptions {
+    ValidationOptions {
+        verify_entry_signatures: Some(true),
+        ..ValidationOptions::default()
+    }
+}
+
+#[test]
+fn verifies_identity_signatures() {
+    verify_entry_signature(&signed_entry()).unwrap();
+
+    let padded = Entry {
+        signature: Some(format!("{}==", SIGNATURE)),
+        ..signed_entry()
+    };
+    verify_entry_signature(&padded).unwrap();
+}
+
+#[test]
+fn rejects_missing_and_forged_signatures() {
+    let unsigned = Entry {
+        signature: None,
+        ..signed_entry()
+    };
+    assert_eq!(
+        verify_entry_signature(&unsigned).unwrap_err().code(),
+        "ENTRY_SIGNATURE_MISSING"
+    );
+
+    let edited = Entry {
+        payload: Some(json!({ "id": "user-2" })),
+        ..signed_entry()
+    };
+    assert_eq!(
+        verify_entry_signature(&edited).unwrap_err().code(),
+        "ENTRY_SIGNATURE_INVALID"
+    );
+
+    let not_a_key = Entry {
+        author: "author-1".to_string(),
+        ..signed_entry()
+    };
+    assert_eq!(
+        verify_entry_signature(&not_a_key).unwrap_err().code(),
+        "ENTRY_SIGNATURE_INVALID"
+    );
+}
+
+#[test]
+fn validate_ledger_checks_signatures_when_enabled() {
+    let ledger = ledger_with(signed_entry());
+    assert!(validate_ledger(&ledger, &verifying()).ok);
+
+    let unsigned = ledger_with(Entry {
+        signature: None,
+        ..signed_entry()
+    });
+    assert!(validate_ledger(&unsigned, &ValidationOptions::default()).ok);
+    let result = validate_ledger(&unsigned, &verifying());
+    assert!(!result.ok);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].code, "ENTRY_SIGNATURE_MISSING");
+    assert_eq!(result.errors[0].path.as_deref(), Some("signature"));
+
+    let mut forged = ledger_with(signed_entry());
+    let entry = forged.entries.values_mut().next().unwrap();
+    entry.payload = Some(json!({ "id": "user-2" }));
+    let result = validate_ledger(&forged, &verifying());
+    assert!(!result.ok);
+    assert!(result
+        .errors
+        .iter()
+        .any(|error| error.code == "ENTRY_SIGNATURE_INVALID"));
+}
@@ -0,0 +1,117 @@
+use concord_protocol_wasm::{
+    append_entry, get_commit_chain, get_replay_entries, validate_ledger, Entry, StagedLedger,
+    ValidationOptions,
+};
+use serde_json::json;
+
+mod common;
+
+use common::{entry, new_ledger};
+
+fn staged_ledger() -> StagedLedger {
+    StagedLedger::new(new_ledger())
+}
+
+#[test]
+fn commit_groups_staged_entries_in_order() {
+    let mut ledger = staged_ledger();
+    let genesis_id = ledger.ledger().head.clone();
+    let first = ledger.stage(entry("user-2")).unwrap();
+    let rest = ledger
+        .stage_many(vec![entry("user-1"), entry("user-3")])
+        .unwrap();
+    assert!(ledger.ledger().entries.is_empty());
+    assert_eq!(
+        ledger.get_replay_entries(true).unwrap(),
+        vec![entry("user-2"), entry("user-1"), entry("user-3")]
+    );
+    assert!(ledger.get_replay_entries(false).unwrap().is_empty());
+
+    let committed = ledger
+        .commit(
+            Some(json!({ "message": "users" })),
+            Some("2026-01-01T00:01:00Z".to_string()),
+        )
+        .unwrap();
+    assert_eq!(
+        committed.commit.entries,
+        vec![first, rest[0].clone(), rest[1].clone()]
+    );
+    assert_eq!(
+        committed.commit.parent.as_deref(),
+        Some(genesis_id.as_str())
+    );
+    assert_eq!(committed.entries.len(), 3);
+    assert_eq!(committed.entries[0].entry, entry("user-2"));
+    assert!(ledger.staged().is_empty());
+
+    let container = ledger.into_ledger();
+    assert_eq!(container.head, committed.commit_id);
+    assert_eq!(
+        get_commit_chain(&container).unwrap(),
+        vec![genesis_id, committed.commit_id]
+    );
+    assert_eq!(get_replay_entries(&container).unwrap().len(), 3);
+    assert!(validate_ledger(&container, &ValidationOptions::default()).ok);
+}
+
+#[test]
+fn commit_requires_staged_entries() {
+    let mut ledger = staged_ledger();
+    let before = ledger.clone();
+    let error = ledger.commit(None, None).unwrap_err();
+    assert_eq!(error.code(), "NOTHING_STAGED");
+    assert_eq!(ledger, before);
+
+    ledger.stage(entry("user-1")).unwrap();
+    ledger.clear_staged();
+    assert_eq!(
+        ledger.commit(None, None).unwrap_err().code(),
+        "NOTHING_STAGED"
+    );
+}
+
+#[test]
+fn staging_rejects_duplicates_atomically() {
+    let mut ledger = staged_ledger();
+    append_entry(ledger.ledger_mut(), entry("user-1")).unwrap();
+    ledger.stage(entry("user-2")).unwrap();
+
+    let error = ledger
+        .stage_many(vec![entry("user-3"), entry("user-1")])
+        .unwrap_err();
+    assert_eq!(error.code(), "DUPLICATE_ENTRY");
+    let error = ledger
+        .stage_many(vec![entry("user-3"), entry("user-3")])
+        .unwrap_err();
+    assert_eq!(error.code(), "DUPLICATE_ENTRY");
+    let error = ledger.stage(entry("user-2")).unwrap_err();
+    assert_eq!(error.code(), "DUPLICATE_ENTRY");
+    assert_eq!(ledger.staged().len(), 1);
+
+    let invalid = Entry {
+        kind: String::new(),
+        ..entry("user-4")
+    };
+    assert_eq!(ledger.stage(invalid).unwrap_err().code(), "INVALID_ENTRY");
+    assert_eq!(ledger.staged().len(), 1);
+}
+
+#[test]
+fn failed_commit_keeps_staging_and_container() {
+    let mut ledger = staged_ledger();
+    ledger.stage(entry("user-1")).unwrap();
+    let before = ledger.clone();
+
+    let error = ledger
+        .commit(Some(json!("not-an-object")), None)
+        .unwrap_err();
+    assert_eq!(error.code(), "INVALID_COMMIT");
+    assert_eq!(ledger, before);
+
+    let error = ledger
+        .commit(Some(json!({ "genesis": true })), None)
+        .unwrap_err();
+    assert_eq!(error.code(), "INVALID_COMMIT");
+    assert_eq!(ledger, before);
+}
@@ -0,0 +1,171 @@
+use concord_protocol_wasm::{
+    append_commit, append_entry, create_commit, validate_commit, validate_entry, validate_ledger,
+    CommitParams, Entry, LedgerContainer, ValidationOptions,
+};
+mod common;
+
+use common::{commit, entry, new_ledger};
+
+fn entry_at(id: &str, timestamp: &str) -> Entry {
+    Entry {
+        timestamp: timestamp.to_string(),
+        ..entry(id)
+    }
+}
+
+fn commit_entry(ledger: &mut LedgerContainer, entry: Entry, timestamp: &str) -> String {
+    let entry_id = append_entry(ledger, entry).unwrap();
+    commit(
+        ledger,
+        CommitParams {
+            entries: vec![entry_id],
+            timestamp: Some(timestamp.to_string()),
+            ..CommitParams::default()
+        },
+    )
+}
+
+fn codes(ledger: &LedgerContainer, options: &ValidationOptions) -> Vec<&'static str> {
+    validate_ledger(ledger, options)
+        .errors
+        .into_iter()
+        .map(|error| error.code)
+        .collect()
+}
+
+#[test]
+fn validators_reject_malformed_timestamps() {
+    let options = ValidationOptions::default();
+    let unchecked = ValidationOptions {
+        check_timestamp_format: Some(false),
+        ..ValidationOptions::default()
+    };
+    assert!(validate_entry(&entry_at("user-1", "2026-01-01T00:00:00+02:00"), &options).ok);
+
+    let malformed = entry_at("user-2", "yesterday");
+    let result = validate_entry(&malformed, &options);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].code, "TIMESTAMP_INVALID");
+    assert_eq!(result.errors[0].path.as_deref(), Some("timestamp"));
+    assert!(validate_entry(&malformed, &unchecked).ok);
+
+    let mut ledger = new_ledger();
+    let mut created = create_commit(&ledger, CommitParams::default()).unwrap();
+    created.commit.timestamp = "2026-01-01 00:02".to_string();
+    let result = validate_commit(&created.commit, &options);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].code, "TIMESTAMP_INVALID");
+    assert!(validate_commit(&created.commit, &unchecked).ok);
+
+    // Appends accept any non-empty timestamp, as the TypeScript reference does.
+    append_entry(&mut ledger, malformed).unwrap();
+    append_commit(&mut ledger, created.commit_id, created.commit).unwrap();
+}
+
+#[test]
+fn validate_ledger_reports_malformed_timestamps() {
+    let mut ledger = new_ledger();
+    commit_entry(
+        &mut ledger,
+        entry_at("user-1", "2026-01-01T00:00:00+02:00"),
+        "2026-01-01T00:01:00.250Z",
+    );
+    assert!(codes(&ledger, &ValidationOptions::default()).is_empty());
+
+    let commit_id = commit_entry(
+        &mut ledger,
+        entry_at("user-2", "yesterday"),
+        "2026-01-01 00:02",
+    );
+    let result = validate_ledger(&ledger, &ValidationOptions::default());
+    let invalid: Vec<_> = result
+        .errors
+        .iter()
+        .filter(|error| error.code == "TIMESTAMP_INVALID")
+        .collect();
+    assert_eq!(invalid.len(), 2);
+    assert!(invalid.iter().any(
+        |error| error.commit_id.as_deref() == Some(commit_id.as_str())
+            && error.path.as_deref() == Some("timestamp")
+    ));
+
+    let options = ValidationOptions {
+        check_timestamp_format: Some(false),
+        ..ValidationOptions::default()
+    };
+    assert!(codes(&ledger, &options).is_empty());
+}
+
+#[test]
+fn commit_timestamps_must_not_decrease() {
+    let mut ledger = new_ledger();
+    commit_entry(
+        &mut ledger,
+        entry_at("user-1", "2026-01-01T00:00:00Z"),
+        "2026-01-01T00:05:00Z",
+    );
+    commit_entry(
+        &mut ledger,
+        entry_at("user-2", "2026-01-01T00:00:00Z"),
+        "2026-01-01T00:05:00Z",
+    );
+    assert!(codes(&ledger, &ValidationOptions::default()).is_empty());
+
+    let commit_id = commit_entry(
+        &mut ledger,
+        entry_at("user-3", "2026-01-01T00:00:00Z"),
+        "2026-01-01T00:04:59Z",
+    );
+    let result = validate_ledger(&ledger, &ValidationOptions::default());
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].code, "COMMIT_TIMESTAMP_DECREASING");
+    assert_eq!(
+        result.errors[0].commit_id.as_deref(),
+        Some(commit_id.as_str())
+    );
+
+    let options = ValidationOptions {
+        check_commit_timestamp_order: Some(false),
+        ..ValidationOptions::default()
+    };
+    assert!(codes(&ledger, &options).is_empty());
+}
+
+#[test]
+fn entries_must_not_postdate_their_commit() {
+    let mut ledger = new_ledger();
+    commit_entry(
+        &mut ledger,
+        entry_at("user-1", "2026-01-01T00:01:00Z"),
+        "2026-01-01T00:01:00Z",
+    );
+    assert!(codes(&ledger, &ValidationOptions::default()).is_empty());
+
+    // Offsets are compared as instants: 01:30+02:00 is 23:30Z the day before.
+    commit_entry(
+        &mut ledger,
+        entry_at("user-2", "2026-01-01T01:30:00+02:00"),
+        "2026-01-01T00:02:00Z",
+    );
+    assert!(codes(&ledger, &ValidationOptions::default()).is_empty());
+
+    let commit_id = commit_entry(
+        &mut ledger,
+        entry_at("user-3", "2026-01-01T00:03:01Z"),
+        "2026-01-01T00:03:00Z",
+    );
+    let result = validate_ledger(&ledger, &ValidationOptions::default());
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].code, "ENTRY_TIMESTAMP_AFTER_COMMIT");
+    assert_eq!(
+        result.errors[0].commit_id.as_deref(),
+        Some(commit_id.as_str())
+    );
+    assert!(result.errors[0].entry_id.is_some());
+
+    let options = ValidationOptions {
+        check_entry_timestamp_order: Some(false),
+        ..ValidationOptions::default()
+    };
+    assert!(codes(&ledger, &options).is_empty());
+}
@@ -0,0 +1,166 @@
+//! Conformance runner for the shared `@ternent/concord-test-vectors` fixtures.
+//!
+//! The fixture file is compiled in, so any drift between the Rust and
+//! TypeScript implementations fails `cargo test`.
+
+use concord_protocol_wasm::{
+    append_entry, canonical_stringify, create_ledger, derive_commit_id, derive_entry_id,
+    get_entry_signing_payload, validate_entry, Commit, Entry, InputValue, ValidationOptions,
+};
+use serde_json::Value;
+
+const VECTORS: &str = include_str!("../../concord-test-vectors/vectors.json");
+
+const KNOWN_TYPES: &[&str] = &[
+    "entry",
+    "entry-signature-exclusion",
+    "commit",
+    "reject-entry",
+];
+
+fn vectors_of(kind: &str) -> Vec<Value> {
+    let vectors: Vec<Value> = serde_json::from_str(VECTORS).unwrap();
+    vectors
+        .into_iter()
+        .filter(|vector| vector["type"] == kind)
+        .collect()
+}
+
+fn expect_str<'a>(vector: &'a Value, key: &str) -> &'a str {
+    vector["expect"][key]
+        .as_str()
+        .unwrap_or_else(|| panic!("{}: missing expect.{}", vector["name"], key))
+}
+
+/// Replaces `__concord_type` markers with the non-JSON values they model, as
+/// the JavaScript vector runner does.
+fn materialize(value: &Value) -> InputValue {
+    match value {
+        Value::Object(map) => match map.get("__concord_type").and_then(Value::as_str) {
+            Some("undefined") => InputValue::Undefined,
+            Some("nan") => InputValue::Number(f64::NAN),
+            Some("infinity") => InputValue::Number(f64::INFINITY),
+            Some("circular") => {
+                InputValue::Object(vec![("self".to_string(), InputValue::Circular)])
+            }
+            Some(other) => panic!("unknown __concord_type marker {}", other),
+            None => InputValue::Object(
+                map.iter()
+                    .map(|(key, child)| (key.clone(), materialize(child)))
+                    .collect(),
+            ),
+        },
+        Value::Array(items) => InputValue::Array(items.iter().map(materialize).collect()),
+        other => InputValue::from(other.clone()),
+    }
+}
+
+#[test]
+fn every_vector_type_is_covered() {
+    let vectors: Vec<Value> = serde_json::from_str(VECTORS).unwrap();
+    assert!(!vectors.is_empty());
+    for vector in &vectors {
+        let kind = vector["type"].as_str().unwrap_or_default();
+        assert!(
+            KNOWN_TYPES.contains(&kind),
+            "{}: vector type {:?} has no Rust runner",
+            vector["name"],
+            kind
+        );
+    }
+}
+
+#[test]
+fn entry_vectors() {
+    let vectors = vectors_of("entry");
+    assert!(!vectors.is_empty());
+    for vector in vectors {
+        let entry = Entry::from_input(materialize(&vector["input"])).unwrap();
+        assert_eq!(
+            derive_entry_id(&entry).unwrap(),
+            expect_str(&vector, "entryId"),
+            "{}",
+            vector["name"]
+        );
+        assert_eq!(
+            get_entry_signing_payload(&entry).unwrap(),
+            expect_str(&vector, "signingPayload"),
+            "{}",
+            vector["name"]
+        );
+        assert!(
+            validate_entry(&entry, &ValidationOptions::default()).ok,
+            "{}",
+            vector["name"]
+        );
+    }
+}
+
+#[test]
+fn signature_exclusion_vectors() {
+    let vectors = vectors_of("entry-signature-exclusion");
+    assert!(!vectors.is_empty());
+    for vector in vectors {
+        for variant in vector["variants"].as_array().unwrap() {
+            let mut input = vector["input"].clone();
+            for (key, value) in variant.as_object().unwrap() {
+                input[key] = value.clone();
+            }
+            let entry = Entry::from_input(materialize(&input)).unwrap();
+            assert!(entry.signature.is_some());
+            assert_eq!(
+                derive_entry_id(&entry).unwrap(),
+                expect_str(&vector, "entryId"),
+                "{}",
+                vector["name"]
+            );
+        }
+    }
+}
+
+#[test]
+fn commit_vectors() {
+    let vectors = vectors_of("commit");
+    assert!(!vectors.is_empty());
+    for vector in vectors {
+        let commit: Commit = serde_json::from_value(vector["input"].clone()).unwrap();
+        assert_eq!(
+            derive_commit_id(&commit).unwrap(),
+            expect_str(&vector, "commitId"),
+            "{}",
+            vector["name"]
+        );
+        assert_eq!(
+            canonical_stringify(&vector["input"]).unwrap(),
+            expect_str(&vector, "canonicalCommit"),
+            "{}",
+            vector["name"]
+        );
+    }
+}
+
+#[test]
+fn rejection_vectors() {
+    let vectors = vectors_of("reject-entry");
+    assert!(!vectors.is_empty());
+    for vector in vectors {
+        let mut ledger = create_ledger(None, None).unwrap();
+        let error = Entry::from_input(materialize(&vector["input"]))
+            .and_then(|entry| append_entry(&mut ledger, entry))
+            .expect_err(vector["name"].as_str().unwrap());
+        assert_eq!(
+            error.code(),
+            expect_str(&vector, "errorCode"),
+            "{}",
+            vector["name"]
+        );
+        assert!(
+            error
+                .message()
+                .contains(expect_str(&vector, "messageIncludes")),
+            "{}: {:?}",
+            vector["name"],
+            error.message()
+        );
+    }
+}